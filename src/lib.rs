@@ -97,13 +97,54 @@
 //! [rustup]: https://rust-lang.github.io/rustup/
 //! [Rustup Overrides]: https://rust-lang.github.io/rustup/overrides.html
 
+use std::collections::{BTreeSet, HashSet};
+use std::convert::TryFrom;
 use std::ffi::{OsStr, OsString};
 use std::fmt;
-use std::path::PathBuf;
-use std::process::{Command, Output};
+use std::fs::{self, File};
+use std::io::Read;
+use std::ops::{Deref, Index};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
 use std::slice::Iter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use std::{env, str::FromStr};
 
+/// The commonly used types and functions.
+///
+/// The types for building the command and for working with its output, the
+/// error type, and the [`host`], [`target`], and [`targets`] functions are
+/// re-exported, so a single glob import covers the majority of use cases.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// use cargo_rustc_cfg::prelude::*;
+///
+/// # fn main() -> std::result::Result<(), Error> {
+/// let host: RustcTargetCfg = host()?;
+/// assert!(host.iter().any(Cfg::is_key_pair));
+/// let targets: Targets = CargoRustcPrintCfg::default()
+///     .rustc_target("i686-pc-windows-msvc")
+///     .execute_targets()?;
+/// assert_eq!(targets.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`host`]: ../fn.host.html
+/// [`target`]: ../fn.target.html
+/// [`targets`]: ../fn.targets.html
+pub mod prelude {
+    pub use crate::{
+        host, target, targets, Arch, CargoRustcPrintCfg, Cfg, Error, Os, RustcTargetCfg, Targets,
+    };
+}
+
 /// The command line name of the Cargo application.
 pub const CARGO: &str = "cargo";
 
@@ -113,8 +154,122 @@ pub const CARGO_VARIABLE: &str = "CARGO";
 /// The command line name of the Rust compiler subcommand for Cargo.
 pub const RUSTC: &str = "rustc";
 
+/// The environment variable name for the Rust compiler (rustc) application.
+pub const RUSTC_VARIABLE: &str = "RUSTC";
+
+/// The environment variable name that overrides the toolchain of the rustup
+/// proxies.
+const RUSTUP_TOOLCHAIN_VARIABLE: &str = "RUSTUP_TOOLCHAIN";
+
+/// The environment variable name for the encoded compiler (rustc) flags.
+const CARGO_ENCODED_RUSTFLAGS: &str = "CARGO_ENCODED_RUSTFLAGS";
+
+/// The separator of the flags in the `CARGO_ENCODED_RUSTFLAGS` environment
+/// variable, i.e. the ASCII Unit Separator character.
+const RUSTFLAGS_SEPARATOR: &str = "\x1f";
+
+/// The environment variable name that overrides the `term.color` Cargo
+/// configuration.
+const CARGO_TERM_COLOR: &str = "CARGO_TERM_COLOR";
+
+/// The environment variable name of the [`NO_COLOR`] convention.
+///
+/// [`NO_COLOR`]: https://no-color.org
+const NO_COLOR: &str = "NO_COLOR";
+
+/// The environment variable name that overrides the `net.offline` Cargo
+/// configuration.
+const CARGO_NET_OFFLINE: &str = "CARGO_NET_OFFLINE";
+
+/// The environment variable names for proxies that enable network access. The
+/// lowercase variants are used as well.
+const PROXY_VARIABLES: [&str; 4] = ["CARGO_HTTP_PROXY", "HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"];
+
+/// The operating systems of target triples that are used without a vendor,
+/// e.g. `aarch64-linux-android`.
+const VENDORLESS_OSES: [&str; 4] = ["linux", "none", "nuttx", "rtems"];
+
+/// The prefix of the WebAssembly System Interface (WASI) operating systems of
+/// target triples, which are used without a vendor, e.g.
+/// `wasm32-wasip1-threads`.
+const WASIP_PREFIX: &str = "wasip";
+
+/// The special target triple that Cargo and rustc replace with the host triple.
+const HOST_TUPLE: &str = "host-tuple";
+
+/// The name of the unstable Cargo feature for specifying multiple targets.
+const MULTITARGET: &str = "multitarget";
+
+/// The kind of information printed by the compiler (rustc) that is parsed into
+/// compiler configurations.
+const PRINT_CFG: &str = "cfg";
+
+/// The kind of information printed by the compiler (rustc) for the sysroot.
+const PRINT_SYSROOT: &str = "sysroot";
+
+/// The kind of information printed by the compiler (rustc) for the target
+/// specification.
+const PRINT_TARGET_SPEC_JSON: &str = "target-spec-json";
+
+/// The kinds of information printed by the compiler (rustc) on a single line,
+/// which can be combined with the compiler configurations in one invocation.
+const SINGLE_LINE_PRINTS: [&str; 3] = [HOST_TUPLE, PRINT_SYSROOT, "target-libdir"];
+
+/// The maximum number of targets for each command when determining the
+/// compiler configurations for all targets.
+const TARGET_BATCH_SIZE: usize = 64;
+
+/// The delay before the first retry of a transient failure, which is doubled
+/// for each subsequent retry.
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The lines in the STDERR stream that note the lint level of a lint, which
+/// turns the lint into an error when denied.
+const LINT_LEVEL_NOTES: [&str; 4] = ["`-D ", "`-F ", "#[deny(", "#[forbid("];
+
+/// The prefixes of error lines in the STDERR stream that summarize the
+/// preceding errors, rather than report an error of their own.
+const ERROR_SUMMARIES: [&str; 2] = ["error: aborting due to", "error: could not compile"];
+
+/// The messages in the STDERR stream of a failed command that indicate a
+/// transient failure, which is retried.
+const TRANSIENT_ERRORS: [&str; 3] = [
+    "failed to acquire package cache lock",
+    "Resource temporarily unavailable",
+    "Interrupted system call",
+];
+
+/// The manifest of the minimal package used when a Cargo project is not
+/// available.
+///
+/// The empty `workspace` table prevents Cargo from searching the parent
+/// directories for a workspace.
+const TEMP_MANIFEST: &str = "[package]
+name = \"cargo-rustc-cfg-query\"
+version = \"0.0.0\"
+edition = \"2018\"
+
+[workspace]
+";
+
 /// Returns the compiler (rustc) configurations for the host.
 ///
+/// The host is always used, regardless of a default target configured with the
+/// `build.target` value in a `.cargo/config.toml` file or the
+/// `CARGO_BUILD_TARGET` environment variable. Use the [`default_target`]
+/// function to honor the configured default target instead.
+///
+/// These are the compiler configurations of a package that is compiled for
+/// the host, including the flags of the `RUSTFLAGS` environment variable and
+/// the `build.rustflags` configuration. They are not always the compiler
+/// configurations of the build dependencies, i.e. build scripts and
+/// procedural macros, which are also compiled for the host. Use the
+/// [`host_for_build`] function for those instead.
+///
+/// An error is returned if the command succeeds but its output does not
+/// contain any compiler configurations. The error message includes a snippet
+/// of the captured output.
+///
 /// # Examples
 ///
 /// ```
@@ -131,13 +286,141 @@ pub const RUSTC: &str = "rustc";
 /// # Ok(())
 /// # }
 /// ```
+///
+/// If the command prints nothing, e.g. with a stub for the `cargo` command:
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # #[cfg(unix)]
+/// # {
+/// std::env::set_var("CARGO", "true");
+/// match cargo_rustc_cfg::host() {
+///     Err(Error::Generic(message)) => assert!(message.contains("no compiler configurations")),
+///     result => panic!("Unexpected result: {:?}", result),
+/// }
+/// # }
+/// ```
+///
+/// [`default_target`]: fn.default_target.html
+/// [`host_for_build`]: fn.host_for_build.html
 pub fn host() -> Result<RustcTargetCfg, Error> {
     CargoRustcPrintCfg::default()
+        .rustc_target(HOST_TUPLE)
+        .execute()?
+        .pop()
+        .ok_or_else(|| Error::from("The host compiler configuration does not exist"))
+}
+
+/// Returns the compiler (rustc) configurations for the host as seen by the
+/// build dependencies.
+///
+/// Build scripts, procedural macros, and their dependencies are compiled for
+/// the host, while normal dependencies are compiled for the target, like the
+/// package. When cross-compiling, i.e. with the `--target` argument, Cargo
+/// does not pass the flags of the `RUSTFLAGS` environment variable and the
+/// `build.rustflags` configuration to the build dependencies, so the flags
+/// are ignored here, too, unlike the [`host`] function. Without the
+/// `--target` argument, Cargo passes the flags to every compiler invocation
+/// and the [`host`] function is the one to use. The unstable
+/// `host.rustflags` configuration is not applied.
+///
+/// Note, the `cargo rustc --print cfg` command ignores the profiles, so
+/// neither function reflects profile settings, such as the `debug-assertions`
+/// and `overflow-checks` values of a profile or its `build-override` table,
+/// which change the `debug_assertions` and `overflow_checks` configurations
+/// of the build dependencies.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// std::env::set_var("RUSTFLAGS", "-C target-feature=+crt-static");
+/// let host = cargo_rustc_cfg::host()?;
+/// let build = cargo_rustc_cfg::host_for_build()?;
+/// assert!(host.key_pairs().any(|p| p == ("target_feature", "crt-static")));
+/// assert!(!build.key_pairs().any(|p| p == ("target_feature", "crt-static")));
+/// assert_eq!(build.target_arch(), host.target_arch());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`host`]: fn.host.html
+pub fn host_for_build() -> Result<RustcTargetCfg, Error> {
+    CargoRustcPrintCfg::default()
+        .rustc_target(HOST_TUPLE)
+        .rustflags(Vec::<OsString>::new())
         .execute()?
         .pop()
         .ok_or_else(|| Error::from("The host compiler configuration does not exist"))
 }
 
+/// Returns the compiler (rustc) configurations for the default target.
+///
+/// The default target is the target Cargo builds for when no `--target`
+/// argument is used. This is the target configured with the `build.target`
+/// value in a `.cargo/config.toml` file or the `CARGO_BUILD_TARGET` environment
+/// variable. If no default target is configured, then it is the host. Use the
+/// [`host`] function to always get the host regardless of the configuration.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// std::env::set_var("CARGO_BUILD_TARGET", "i686-pc-windows-msvc");
+/// let default_target = cargo_rustc_cfg::default_target()?;
+/// assert_eq!(default_target.target_os(), Some("windows"));
+/// assert_eq!(default_target.target_arch(), Some("x86"));
+///
+/// let host = cargo_rustc_cfg::host()?;
+/// assert_ne!(host, default_target);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`host`]: fn.host.html
+pub fn default_target() -> Result<RustcTargetCfg, Error> {
+    CargoRustcPrintCfg::default()
+        .execute()?
+        .pop()
+        .ok_or_else(|| Error::from("The default target compiler configuration does not exist"))
+}
+
+/// Returns the compiler (rustc) configurations for every target the project is
+/// configured to build, labeled with their target triples.
+///
+/// The targets are the `build.target` value, which can be a list of targets,
+/// in a `.cargo/config.toml` file or the `CARGO_BUILD_TARGET` environment
+/// variable, and the host if no target is configured. Unlike the
+/// [`default_target`] function, every configured target is returned. See the
+/// [`execute_configured_targets`] method for how the configuration is
+/// resolved and its limitations.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// std::env::set_var("CARGO_BUILD_TARGET", "i686-pc-windows-msvc");
+/// let targets = cargo_rustc_cfg::configured_targets()?;
+/// assert_eq!(targets.len(), 1);
+/// let msvc = targets.get("i686-pc-windows-msvc").expect("i686-pc-windows-msvc target");
+/// assert_eq!(msvc, &cargo_rustc_cfg::default_target()?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`default_target`]: fn.default_target.html
+/// [`execute_configured_targets`]: struct.CargoRustcPrintCfg.html#method.execute_configured_targets
+pub fn configured_targets() -> Result<Targets, Error> {
+    CargoRustcPrintCfg::default().execute_configured_targets()
+}
+
 /// Returns the compiler (rustc) configurations for a specific target.
 ///
 /// A compiler target's "triple" from the `rustc --print target-list` should be
@@ -177,14 +460,12 @@ where
 /// A compiler target's "triple" from the `rustc --print target-list` should be
 /// used.
 ///
-/// **Note**, the `multitarget` feature must be enabled and currently requires
-/// the nightly toolchain, which can be done by adding the following section to
-/// the project's `./cargo/config.toml` file:
+/// **Note**, the `multitarget` feature is required and currently requires the
+/// nightly toolchain. The `-Z multitarget` option is automatically added to the
+/// command invocation when more than one target is specified.
 ///
-/// ```toml
-/// [unstable]
-/// multitarget = true
-/// ```
+/// The compiler configurations are labeled by target triple, so they can be
+/// retrieved by triple instead of relying on the order of the output.
 ///
 /// # Examples
 ///
@@ -193,8 +474,8 @@ where
 /// # use cargo_rustc_cfg::Error;
 /// # fn main() -> std::result::Result<(), Error> {
 /// let targets = cargo_rustc_cfg::targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])?;
-/// let gnu = targets.get(0).expect("i686-pc-windows-gnu target");
-/// let msvc = targets.get(1).expect("i686-pc-windows-msvc target");
+/// let gnu = targets.get("i686-pc-windows-gnu").expect("i686-pc-windows-gnu target");
+/// let msvc = targets.get("i686-pc-windows-msvc").expect("i686-pc-windows-msvc target");
 ///
 /// assert_eq!(msvc.get("target_arch"), Some("x86"));
 /// assert_eq!(msvc.get("target_endian"), Some("little"));
@@ -216,153 +497,589 @@ where
 /// # Ok(())
 /// # }
 /// ```
-pub fn targets<T>(t: &[T]) -> Result<Vec<RustcTargetCfg>, Error>
+pub fn targets<T>(t: &[T]) -> Result<Targets, Error>
 where
     T: AsRef<OsStr>,
 {
-    CargoRustcPrintCfg::default().rustc_targets(t).execute()
+    CargoRustcPrintCfg::default()
+        .rustc_targets(t)
+        .execute_targets()
 }
 
-/// A builder type for the `cargo rustc --print cfg` command.
+/// Returns the compiler (rustc) configurations for multiple targets, where
+/// each target is queried independently.
 ///
-/// For reference, the default command signature is:
+/// Unlike the [`targets`] function, the `cargo rustc --print cfg` command is
+/// executed once for each target without the `-Z multitarget` feature, so an
+/// invalid or unsupported target does not prevent the compiler
+/// configurations for the other targets from being returned. The results are
+/// in the same order as the targets, including duplicates.
 ///
-/// ```text
-/// cargo rustc --print cfg
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// let results = cargo_rustc_cfg::try_targets(&["i686-pc-windows-msvc", "not-a-real-target"]);
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(results[0].as_ref().unwrap().target_env(), Some("msvc"));
+/// assert!(results[1].is_err());
 /// ```
 ///
-/// and the more generic command signature represented by this type is:
+/// [`targets`]: fn.targets.html
+pub fn try_targets<T>(t: &[T]) -> Vec<Result<RustcTargetCfg, Error>>
+where
+    T: AsRef<OsStr>,
+{
+    t.iter()
+        .map(|triple| {
+            CargoRustcPrintCfg::default()
+                .rustc_target(triple)
+                .execute()?
+                .pop()
+                .ok_or_else(|| Error::from("The target compiler configuration does not exist"))
+        })
+        .collect()
+}
+
+/// Returns the compiler (rustc) configurations for every target known to the
+/// compiler (nightly-only).
 ///
-/// ```text
-/// cargo +<TOOLCHAIN> rustc <CARGO_ARGS> <RUSTC_TARGET> --print cfg -- <RUSTC_ARGS>
-/// ```
+/// The targets are listed with the `rustc --print target-list` command. The
+/// standard library does not need to be installed for a target to determine
+/// its compiler configuration. This is a heavyweight operation, but it is
+/// useful to build a compatibility matrix or to document platform support
+/// across all targets.
 ///
-/// where `<TOOLCHAIN>` is replaced with the [`cargo_toolchain`] value, the
-/// `<CARGO_ARGS>` is replaced with the [`cargo_args`] value, the
-/// `<RUSTC_TARGET>` is replaced with the [`rustc_target`] value, and the
-/// `<RUSTC_ARGS>` is replaced with the [`rustc_args`] value.
+/// See the [`execute_all_targets`] method for more information.
 ///
-/// [`cargo_toolchain`]: #method.cargo_toolchain
-/// [`cargo_args`]: #method.cargo_args
-/// [`rustc_target`]: #method.rustc_target
-/// [`rustc_args`]: #method.rustc_args
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct CargoRustcPrintCfg {
-    cargo_args: Vec<OsString>,
-    cargo_toolchain: Option<OsString>,
-    manifest_path: Option<PathBuf>,
-    rustc_args: Vec<OsString>,
-    rustc_targets: Vec<OsString>,
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let targets = cargo_rustc_cfg::all_installed_targets()?;
+/// assert!(targets.len() > 100);
+/// let wasm = targets.get("wasm32-unknown-unknown").expect("wasm32-unknown-unknown target");
+/// assert_eq!(wasm.get("target_family"), Some("wasm"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`execute_all_targets`]: struct.CargoRustcPrintCfg.html#method.execute_all_targets
+pub fn all_installed_targets() -> Result<Targets, Error> {
+    CargoRustcPrintCfg::default().execute_all_targets()
 }
 
-impl CargoRustcPrintCfg {
-    /// Adds arguments to the Cargo command after the `rustc` subcommand but
-    /// before `--print cfg` argument.
-    ///
-    /// For reference, the default command is:
-    ///
-    /// ```text
-    /// cargo rustc --print cfg
-    /// ```
-    ///
-    /// and this method adds arguments between `rustc` and `--print cfg` to yield:
-    ///
-    /// ```text
-    /// cargo rustc <CARGO_ARGS> --print cfg
-    /// ```
-    ///
-    /// # Examples
-    ///
-    /// Adding the `s` argument to the command. Note, the `-Z
-    /// unstable-options` is only available with the nightly channel:
-    ///
-    /// ```
-    /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
-    /// # fn main() -> std::result::Result<(), Error> {
-    /// let host = CargoRustcPrintCfg::default()
-    ///     .cargo_args(&["-Z", "unstable-options"])
-    ///     .execute()?
-    ///     .pop()
-    ///     .expect("Compiler confugiration");
-    /// assert!(host.has("target_arch"));
-    /// assert!(host.has("target_env"));
-    /// assert!(host.has("target_family"));
-    /// assert!(host.has("target_os"));
-    /// assert!(host.has("target_pointer_width"));
-    /// assert!(host.has("target_vendor"));
-    /// # Ok(())
-    /// # }
-    /// ```
-    ///
-    /// Adding the `-Z multitarget` argument to the command. Note, the `-Z
-    /// multitarget` is only available with the nightly channel:
-    ///
-    /// ```
-    /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
-    /// # fn main() -> std::result::Result<(), Error> {
-    /// let host = CargoRustcPrintCfg::default()
-    ///     .cargo_args(&["-Z", "multitarget"])
-    ///     .execute()?
-    ///     .pop()
-    ///     .expect("Compiler confugiration");
-    /// assert!(host.has("target_arch"));
-    /// assert!(host.has("target_env"));
-    /// assert!(host.has("target_family"));
-    /// assert!(host.has("target_os"));
-    /// assert!(host.has("target_pointer_width"));
-    /// assert!(host.has("target_vendor"));
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn cargo_args<A, S>(&mut self, a: A) -> &mut Self
-    where
-        A: IntoIterator<Item = S>,
-        S: AsRef<OsStr>,
-    {
-        self.cargo_args = a.into_iter().map(|s| s.as_ref().into()).collect();
-        self
+/// Returns the compiler (rustc) configurations for both the host and a specific
+/// target from a single invocation (nightly-only).
+///
+/// A compiler target's "triple" from the `rustc --print target-list` should be
+/// used. The host configuration applies to code compiled for the host, like
+/// build scripts and procedural macros, while the target configuration applies
+/// to the code compiled for the target. This is equivalent to calling the
+/// [`host`] and [`target`] functions, but only one `cargo rustc` process is
+/// launched instead of two. If the target is the host, then both
+/// configurations are the same.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let cfgs = cargo_rustc_cfg::host_and_target("i686-pc-windows-msvc")?;
+/// assert_eq!(cfgs.host, cargo_rustc_cfg::host()?);
+/// assert_eq!(cfgs.target.target_arch(), Some("x86"));
+/// assert_eq!(cfgs.target.target_os(), Some("windows"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`host`]: fn.host.html
+/// [`target`]: fn.target.html
+pub fn host_and_target<T>(triple: T) -> Result<HostAndTarget, Error>
+where
+    T: AsRef<OsStr>,
+{
+    let mut cfgs = CargoRustcPrintCfg::default()
+        .include_host(true)
+        .rustc_target(triple)
+        .execute()?
+        .into_iter();
+    match (cfgs.next(), cfgs.next()) {
+        (Some(host), Some(target)) => Ok(HostAndTarget { host, target }),
+        (Some(host), None) => Ok(HostAndTarget {
+            target: host.clone(),
+            host,
+        }),
+        _ => Err(Error::from(
+            "The host and target compiler configurations do not exist",
+        )),
     }
+}
 
-    /// Specify a toolchain to use.
-    ///
-    /// The toolchain must be installed on the host system before specifying it
-    /// with this method. It is recommended to install and manage various
-    /// toolchains using the [`rustup`] application.
-    ///
-    /// The plus sign, `+`, is prepended automatically. Please do not include it
-    /// as part of the toolchain value.
-    ///
-    /// For reference, the default command is:
-    ///
-    /// ```text
-    /// cargo rustc --print cfg
-    /// ```
-    ///
-    /// and this method would add `+<TOOLCHAIN>` between `cargo` and `rustc` to
-    /// yield:
-    ///
-    /// ```text
-    /// cargo +<TOOLCHAIN> rustc --print cfg
-    /// ```
-    ///
-    /// [`rustup`]: https://rust-lang.github.io/rustup/
-    pub fn cargo_toolchain<T>(&mut self, t: T) -> &mut Self
-    where
-        T: AsRef<OsStr>,
-    {
-        self.cargo_toolchain = Some(t.as_ref().into());
-        self
+/// Returns the compiler (rustc) configurations for a target from multiple
+/// toolchains.
+///
+/// The `cargo rustc --print cfg` command is executed once for each toolchain,
+/// which must be installed, e.g. with [`rustup`]. Each compiler configuration
+/// is labeled with its toolchain, in the same order as the toolchains. This is
+/// useful to compare the compiler configurations across toolchains, e.g. to
+/// detect regressions between releases.
+///
+/// Note, a toolchain is selected with the `+<TOOLCHAIN>` argument, which is
+/// only supported by the `cargo` proxy of `rustup`, not by the `cargo`
+/// application of a specific toolchain, e.g. the value of the `CARGO`
+/// environment variable within a Cargo build.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// # std::env::remove_var("CARGO");
+/// let cfgs = cargo_rustc_cfg::toolchains(&["nightly"], "i686-pc-windows-msvc")?;
+/// assert_eq!(cfgs.len(), 1);
+/// assert_eq!(cfgs[0].0, "nightly");
+/// assert_eq!(cfgs[0].1.target_arch(), Some("x86"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`rustup`]: https://rust-lang.github.io/rustup/
+pub fn toolchains<T, U>(toolchains: &[T], triple: U) -> Result<Vec<(String, RustcTargetCfg)>, Error>
+where
+    T: AsRef<OsStr>,
+    U: AsRef<OsStr>,
+{
+    toolchains
+        .iter()
+        .map(|toolchain| {
+            let cfg = CargoRustcPrintCfg::default()
+                .cargo_toolchain(toolchain)
+                .rustc_target(&triple)
+                .execute()?
+                .pop()
+                .ok_or_else(|| Error::from("The target compiler configuration does not exist"))?;
+            Ok((toolchain.as_ref().to_string_lossy().into_owned(), cfg))
+        })
+        .collect()
+}
+
+/// Parses the output of the `cargo rustc --print cfg` command.
+///
+/// This is the basis of the parser used by the [`execute`] method of the
+/// [`CargoRustcPrintCfg`] builder, but it is decoupled from spawning the
+/// process. It is useful when the command is executed through a custom
+/// process-spawning layer, i.e. a sandbox, and only the captured output should
+/// be parsed. The output for multiple targets is separated by blank lines, and
+/// one compiler configuration container is returned for each target. Empty
+/// blocks, i.e. leading, trailing, or repeated blank lines, are skipped, so an
+/// empty compiler configuration container is never returned. Use the
+/// [`parse_output_with_count`] function if the number of targets is known.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let stdout = "target_arch=\"x86\"\ntarget_os=\"windows\"\nwindows\n\n\
+///               target_arch=\"x86_64\"\ntarget_os=\"linux\"\nunix\n";
+/// let targets = cargo_rustc_cfg::parse_output(stdout)?;
+/// assert_eq!(targets.len(), 2);
+/// assert_eq!(targets[0].target_os(), Some("windows"));
+/// assert_eq!(targets[1].target_os(), Some("linux"));
+///
+/// let stdout = "\n\ntarget_os=\"windows\"\n\n\n\ntarget_os=\"linux\"\n\n";
+/// let targets = cargo_rustc_cfg::parse_output(stdout)?;
+/// assert_eq!(targets.len(), 2);
+/// assert_eq!(targets[0].target_os(), Some("windows"));
+/// assert_eq!(targets[1].target_os(), Some("linux"));
+///
+/// assert!(cargo_rustc_cfg::parse_output("\n\n")?.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`execute`]: struct.CargoRustcPrintCfg.html#method.execute
+/// [`CargoRustcPrintCfg`]: struct.CargoRustcPrintCfg.html
+/// [`parse_output_with_count`]: fn.parse_output_with_count.html
+pub fn parse_output(stdout: &str) -> Result<Vec<RustcTargetCfg>, Error> {
+    let mut cfgs = Vec::new();
+    let mut targets = Vec::new();
+    for line in stdout.lines() {
+        if line.trim().is_empty() {
+            if !cfgs.is_empty() {
+                targets.push(RustcTargetCfg(std::mem::take(&mut cfgs)));
+            }
+        } else {
+            cfgs.push(line.parse::<Cfg>()?);
+        }
+    }
+    if !cfgs.is_empty() {
+        targets.push(RustcTargetCfg(cfgs));
     }
+    Ok(targets)
+}
 
-    /// Sets the path to a package's manifest (Cargo.toml) to determine the
-    /// compiler configuration.
+/// Parses the output of the `cargo rustc --print cfg` command for a known
+/// number of targets.
+///
+/// This is the same as the [`parse_output`] function, but the number of
+/// targets is used to group the compiler configurations robustly instead of
+/// relying solely on blank lines, which may be altered when the output is
+/// piped through other tools. If the output has no blank lines at all and
+/// the count is more than one, then the compiler configurations are regrouped
+/// based on the sort order of the output, where the compiler currently sorts
+/// the configurations of each target, so a configuration that sorts before,
+/// or is the same as, the previous one starts the next target. The compiler
+/// does not guarantee the sort order, so the regrouping is only used if it
+/// matches the count and every group has exactly one `target_arch`
+/// configuration, which the compiler prints for every target. Otherwise, the
+/// blocks separated by blank lines are returned, so the caller can still
+/// report the mismatch.
+///
+/// This is the parser used by the [`execute`] method of the
+/// [`CargoRustcPrintCfg`] builder for the requested targets.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let stdout = "target_arch=\"x86\"\ntarget_os=\"windows\"\nwindows\n\
+///               target_arch=\"x86_64\"\ntarget_os=\"linux\"\nunix\n";
+/// assert_eq!(cargo_rustc_cfg::parse_output(stdout)?.len(), 1);
+/// let targets = cargo_rustc_cfg::parse_output_with_count(stdout, 2)?;
+/// assert_eq!(targets.len(), 2);
+/// assert_eq!(targets[0].target_os(), Some("windows"));
+/// assert_eq!(targets[1].target_os(), Some("linux"));
+///
+/// let stdout = "target_os=\"windows\"\n\ntarget_os=\"linux\"\n";
+/// assert_eq!(cargo_rustc_cfg::parse_output_with_count(stdout, 2)?.len(), 2);
+/// assert_eq!(cargo_rustc_cfg::parse_output_with_count(stdout, 3)?.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The regrouping does not apply to the unsorted output of a single target,
+/// even though it would yield the expected count:
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let stdout = "target_os=\"linux\"\ntarget_arch=\"x86_64\"\nunix\n";
+/// let targets = cargo_rustc_cfg::parse_output_with_count(stdout, 2)?;
+/// assert_eq!(targets.len(), 1);
+/// assert_eq!(targets[0].target_os(), Some("linux"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`parse_output`]: fn.parse_output.html
+/// [`execute`]: struct.CargoRustcPrintCfg.html#method.execute
+/// [`CargoRustcPrintCfg`]: struct.CargoRustcPrintCfg.html
+pub fn parse_output_with_count(stdout: &str, count: usize) -> Result<Vec<RustcTargetCfg>, Error> {
+    let targets = parse_output(stdout)?;
+    if targets.len() == count || targets.len() != 1 {
+        return Ok(targets);
+    }
+    let mut regrouped: Vec<Vec<Cfg>> = Vec::new();
+    let mut previous: Option<String> = None;
+    for cfg in targets.iter().flat_map(|t| t.iter()) {
+        let line = cfg.to_string();
+        match (regrouped.last_mut(), &previous) {
+            (Some(cfgs), Some(p)) if *p < line => cfgs.push(cfg.clone()),
+            _ => regrouped.push(vec![cfg.clone()]),
+        }
+        previous = Some(line);
+    }
+    let is_plausible = regrouped.iter().all(|cfgs| {
+        cfgs.iter()
+            .filter(|c| c.key() == Some("target_arch"))
+            .count()
+            == 1
+    });
+    if regrouped.len() == count && is_plausible {
+        Ok(regrouped.into_iter().map(RustcTargetCfg).collect())
+    } else {
+        Ok(targets)
+    }
+}
+
+/// Parses previously captured output of the `cargo rustc --print cfg` command
+/// from a reader.
+///
+/// This is useful for offline analysis of archived output without running
+/// Cargo again. The output may contain the compiler configurations for
+/// multiple targets, which are separated by blank lines. See the
+/// [`parse_output`] function for more information.
+///
+/// # Errors
+///
+/// An error is returned if reading fails, the content is not valid UTF-8, or
+/// the content cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let recorded: &[u8] = b"debug_assertions\n\
+///     target_arch=\"x86\"\n\
+///     target_env=\"gnu\"\n\
+///     target_os=\"windows\"\n\
+///     windows\n\
+///     \n\
+///     debug_assertions\n\
+///     target_arch=\"x86\"\n\
+///     target_env=\"msvc\"\n\
+///     target_os=\"windows\"\n\
+///     windows\n";
+/// let targets = cargo_rustc_cfg::from_reader(recorded)?;
+/// assert_eq!(targets.len(), 2);
+/// assert_eq!(targets[0].target_env(), Some("gnu"));
+/// assert_eq!(targets[1].target_env(), Some("msvc"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`parse_output`]: fn.parse_output.html
+pub fn from_reader<R>(mut reader: R) -> Result<Vec<RustcTargetCfg>, Error>
+where
+    R: Read,
+{
+    let mut stdout = String::new();
+    reader.read_to_string(&mut stdout)?;
+    parse_output(&stdout)
+}
+
+/// Parses previously captured output of the `cargo rustc --print cfg` command
+/// from a file.
+///
+/// See the [`from_reader`] function for more information.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let path = std::env::temp_dir().join(format!("cfg-{}.txt", std::process::id()));
+/// std::fs::write(&path, "target_arch=\"x86_64\"\ntarget_os=\"linux\"\nunix\n")?;
+/// let targets = cargo_rustc_cfg::from_file(&path)?;
+/// assert_eq!(targets.len(), 1);
+/// assert_eq!(targets[0].target_arch(), Some("x86_64"));
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`from_reader`]: fn.from_reader.html
+pub fn from_file<P>(path: P) -> Result<Vec<RustcTargetCfg>, Error>
+where
+    P: AsRef<Path>,
+{
+    from_reader(File::open(path)?)
+}
+
+/// The compiler configurations of each target, preceded by the lines of the
+/// additional print kinds.
+type Blocks = Vec<(Vec<String>, RustcTargetCfg)>;
+
+/// Parses the output into the block of each target, where the first lines of
+/// each block are the output of the additional print kinds.
+///
+/// The blocks are separated by blank lines. The lines of the print kinds are
+/// kept verbatim, e.g. a path with an equal sign is not a compiler
+/// configuration.
+fn parse_output_with_prints(stdout: &str, prints: usize) -> Result<Blocks, Error> {
+    let mut blocks = Vec::new();
+    let mut lines = Vec::new();
+    for line in stdout.lines().chain(std::iter::once("")) {
+        if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        } else if !lines.is_empty() {
+            if lines.len() < prints {
+                return Err(Error::from(
+                    "The output of the additional print kinds does not exist",
+                ));
+            }
+            let cfgs = lines
+                .split_off(prints)
+                .iter()
+                .map(|l| l.parse::<Cfg>())
+                .collect::<Result<Vec<Cfg>, Error>>()?;
+            blocks.push((std::mem::take(&mut lines), RustcTargetCfg(cfgs)));
+        }
+    }
+    Ok(blocks)
+}
+
+/// Returns a unique path for a temporary directory of this process.
+fn unique_temp_dir() -> PathBuf {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    env::temp_dir().join(format!(
+        "cargo-rustc-cfg-{}-{}",
+        process::id(),
+        COUNT.fetch_add(1, Ordering::SeqCst)
+    ))
+}
+
+/// Mirrors the Rust source files of a directory recursively as empty files.
+///
+/// Cargo only needs the source files to discover the targets of a package,
+/// not their content, to print the compiler configurations. Symbolic links
+/// are followed, where a broken link is skipped, and so is a linked directory
+/// that is already being visited, which would be a cycle.
+fn stub_sources(from: &Path, to: &Path, visited: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let canonical = fs::canonicalize(from)?;
+    if visited.contains(&canonical) {
+        return Ok(());
+    }
+    visited.push(canonical);
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        if let Some(name) = path.file_name() {
+            if metadata.is_dir() {
+                stub_sources(&path, &to.join(name), visited)?;
+            } else if path.extension() == Some(OsStr::new("rs")) {
+                fs::write(to.join(name), "")?;
+            }
+        }
+    }
+    visited.pop();
+    Ok(())
+}
+
+/// Returns the beginning of captured output for an error message.
+fn snippet(output: &str) -> String {
+    const MAX_CHARS: usize = 200;
+    let output = output.trim();
+    if output.chars().count() > MAX_CHARS {
+        format!("{}...", output.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        output.to_string()
+    }
+}
+
+/// Returns the configurations that are in `a` but not in `b`, where each
+/// configuration in `b` cancels at most one equal configuration in `a`.
+fn only_in<'a>(a: &'a [Cfg], b: &[Cfg]) -> Vec<&'a Cfg> {
+    let mut matched = vec![false; b.len()];
+    a.iter()
+        .filter(
+            |c| match (0..b.len()).find(|&i| !matched[i] && b[i] == **c) {
+                Some(i) => {
+                    matched[i] = true;
+                    false
+                }
+                None => true,
+            },
+        )
+        .collect()
+}
+
+/// A builder type for the `cargo rustc --print cfg` command.
+///
+/// For reference, the default command signature is:
+///
+/// ```text
+/// cargo rustc --print cfg
+/// ```
+///
+/// and the more generic command signature represented by this type is:
+///
+/// ```text
+/// cargo +<TOOLCHAIN> rustc <CARGO_ARGS> <RUSTC_TARGET> --print cfg -- <RUSTC_ARGS>
+/// ```
+///
+/// where `<TOOLCHAIN>` is replaced with the [`cargo_toolchain`] value, the
+/// `<CARGO_ARGS>` is replaced with the [`cargo_args`] value, the
+/// `<RUSTC_TARGET>` is replaced with the [`rustc_target`] value, and the
+/// `<RUSTC_ARGS>` is replaced with the [`rustc_args`] value.
+///
+/// The `Debug` implementation renders the effective command, while the
+/// alternate form, i.e. `{:#?}`, renders the options.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::CargoRustcPrintCfg;
+/// let mut builder = CargoRustcPrintCfg::default();
+/// builder.rustc_target("x86_64-unknown-linux-gnu");
+/// let command = format!("{:?}", builder);
+/// assert!(command.ends_with(
+///     r#""rustc" "--target" "x86_64-unknown-linux-gnu" "--print" "cfg""#
+/// ));
+/// let options = format!("{:#?}", builder);
+/// assert!(options.starts_with("CargoRustcPrintCfg {"));
+/// assert!(options.contains("rustc_targets: ["));
+/// ```
+///
+/// [`cargo_toolchain`]: #method.cargo_toolchain
+/// [`cargo_args`]: #method.cargo_args
+/// [`rustc_target`]: #method.rustc_target
+/// [`rustc_args`]: #method.rustc_args
+#[derive(Clone, Default, PartialEq)]
+pub struct CargoRustcPrintCfg {
+    bins: Vec<OsString>,
+    cargo_args: Vec<OsString>,
+    cargo_toolchain: Option<OsString>,
+    color: Option<OsString>,
+    command_hook: Option<CommandHook>,
+    configs: Vec<OsString>,
+    crate_type: Option<OsString>,
+    edition: Option<OsString>,
+    examples: Vec<OsString>,
+    frozen: bool,
+    hermetic: bool,
+    ignore_rust_version: bool,
+    include_host: bool,
+    lib: bool,
+    locked: bool,
+    manifest_path: Option<PathBuf>,
+    max_stderr_lines: Option<usize>,
+    no_manifest: bool,
+    no_multitarget_fallback: bool,
+    no_workspace: bool,
+    print_kind: Option<OsString>,
+    program: Option<PathBuf>,
+    quiet: bool,
+    retries: usize,
+    retry_backoff: Option<Duration>,
+    runner: Option<Runner>,
+    rustc_args: Vec<OsString>,
+    rustc_sysroot: Option<PathBuf>,
+    rustc_targets: Vec<OsString>,
+    rustflags: Option<Vec<OsString>>,
+    rustup_toolchain: bool,
+    sanitizers: Vec<OsString>,
+    subcommand: Option<OsString>,
+    target_dir: Option<PathBuf>,
+    unstable_flags: Vec<OsString>,
+    verbose: u8,
+}
+
+impl CargoRustcPrintCfg {
+    /// Selects a binary of the package by name.
     ///
-    /// The default assumes the current working directory (CWD) contains the
-    /// package's manifest, i.e. at the root directory of the Cargo project. Use
-    /// this method to override this default and determine the compiler
-    /// configuration for a Cargo-based project outside of the CWD.
+    /// This can be called multiple times to select multiple binaries. Selecting
+    /// what to compile can matter when different crate types imply different
+    /// compiler configurations.
     ///
     /// For reference, the default command is:
     ///
@@ -370,23 +1087,37 @@ impl CargoRustcPrintCfg {
     /// cargo rustc --print cfg
     /// ```
     ///
-    /// and this method adds the `--manifest-path` argument to yield:
+    /// and this method adds the `--bin <NAME>` argument to yield:
     ///
     /// ```text
-    /// cargo rustc  --manifest-path <PATH> --print cfg
+    /// cargo rustc --bin <NAME> --print cfg
     /// ```
     ///
-    /// where `<PATH>` is replaced with a path to a package's manifest
-    /// (Cargo.toml).
-    pub fn manifest_path<P>(&mut self, p: P) -> &mut Self
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .bin("cargo-example")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bin<S>(&mut self, name: S) -> &mut Self
     where
-        P: Into<PathBuf>,
+        S: AsRef<OsStr>,
     {
-        self.manifest_path = Some(p.into());
+        self.bins.push(name.as_ref().into());
         self
     }
 
-    /// Adds arguments to the Cargo command after the `--` flag.
+    /// Adds arguments to the Cargo command after the `rustc` subcommand but
+    /// before `--print cfg` argument.
     ///
     /// For reference, the default command is:
     ///
@@ -394,382 +1125,6288 @@ impl CargoRustcPrintCfg {
     /// cargo rustc --print cfg
     /// ```
     ///
-    /// and this method adds arguments after `--` to yield:
+    /// and this method adds arguments between `rustc` and `--print cfg` to yield:
     ///
     /// ```text
-    /// cargo rustc --print cfg -- <RUSTC_ARGS>
+    /// cargo rustc <CARGO_ARGS> --print cfg
     /// ```
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// An error may occur if the `--print cfg` argument is used as a rustc argument.
-    pub fn rustc_args<A, S>(&mut self, a: A) -> &mut Self
+    /// Adding the `s` argument to the command. Note, the `-Z
+    /// unstable-options` is only available with the nightly channel:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .cargo_args(&["-Z", "unstable-options"])
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler confugiration");
+    /// assert!(host.has("target_arch"));
+    /// assert!(host.has("target_env"));
+    /// assert!(host.has("target_family"));
+    /// assert!(host.has("target_os"));
+    /// assert!(host.has("target_pointer_width"));
+    /// assert!(host.has("target_vendor"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Adding the `-Z multitarget` argument to the command. Note, the `-Z
+    /// multitarget` is only available with the nightly channel:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .cargo_args(&["-Z", "multitarget"])
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler confugiration");
+    /// assert!(host.has("target_arch"));
+    /// assert!(host.has("target_env"));
+    /// assert!(host.has("target_family"));
+    /// assert!(host.has("target_os"));
+    /// assert!(host.has("target_pointer_width"));
+    /// assert!(host.has("target_vendor"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cargo_args<A, S>(&mut self, a: A) -> &mut Self
     where
         A: IntoIterator<Item = S>,
         S: AsRef<OsStr>,
     {
-        self.rustc_args = a.into_iter().map(|s| s.as_ref().into()).collect();
+        self.cargo_args = a.into_iter().map(|s| s.as_ref().into()).collect();
         self
     }
 
-    /// Specify a Rust compiler (rustc) target via a target triple.
+    /// Specify a toolchain to use.
+    ///
+    /// The toolchain must be installed on the host system before specifying it
+    /// with this method. It is recommended to install and manage various
+    /// toolchains using the [`rustup`] application.
+    ///
+    /// The plus sign, `+`, is prepended automatically. If the toolchain value
+    /// already starts with a plus sign, e.g. `+nightly`, it is removed, so
+    /// the toolchain argument only ever has one.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method would add `+<TOOLCHAIN>` between `cargo` and `rustc` to
+    /// yield:
+    ///
+    /// ```text
+    /// cargo +<TOOLCHAIN> rustc --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::CargoRustcPrintCfg;
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.cargo_toolchain("+nightly");
+    /// let command = format!("{:?}", builder);
+    /// assert!(command.contains(r#" "+nightly" "rustc""#));
+    /// assert!(!command.contains("++"));
+    /// ```
+    ///
+    /// [`rustup`]: https://rust-lang.github.io/rustup/
+    pub fn cargo_toolchain<T>(&mut self, t: T) -> &mut Self
+    where
+        T: AsRef<OsStr>,
+    {
+        let t = t.as_ref();
+        self.cargo_toolchain = Some(
+            t.to_str()
+                .and_then(|s| s.strip_prefix('+'))
+                .map_or_else(|| t.into(), OsString::from),
+        );
+        self
+    }
+
+    /// Returns the version of Cargo.
+    ///
+    /// This executes the `cargo --version` command with the same program and
+    /// toolchain as the `cargo rustc --print cfg` command, i.e. the
+    /// [`program`] and [`cargo_toolchain`] values and the `CARGO` environment
+    /// variable are honored, and returns its trimmed output. This is useful
+    /// for provenance or to determine which features are available before
+    /// using them.
+    ///
+    /// For reference, the command is:
+    ///
+    /// ```text
+    /// cargo +<TOOLCHAIN> --version
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let version = CargoRustcPrintCfg::default().cargo_version()?;
+    /// assert!(version.starts_with("cargo "));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`program`]: #method.program
+    /// [`cargo_toolchain`]: #method.cargo_toolchain
+    pub fn cargo_version(&self) -> Result<String, Error> {
+        let mut cmd = self.cargo_command();
+        cmd.arg("--version");
+        let output = self.run(&mut cmd)?;
+        if !output.status.success() {
+            return Err(self.command_error(&cmd, output));
+        }
+        let stdout = String::from_utf8(output.stdout).map_err(|source| Error::Output {
+            command: format!("{:?}", cmd),
+            source,
+        })?;
+        Ok(stdout.trim().to_string())
+    }
+
+    /// Adds a `--config` override for a Cargo configuration value.
+    ///
+    /// This can be called multiple times to add multiple overrides. Each
+    /// override is either a `KEY=VALUE` pair in TOML syntax or a path to an
+    /// extra configuration file. See the [Cargo configuration] documentation
+    /// for more information.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds a `--config <ENTRY>` argument for each entry to
+    /// yield:
+    ///
+    /// ```text
+    /// cargo rustc --config <ENTRY> --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// Adding a target feature through the `build.rustflags` configuration
+    /// value instead of the `RUSTFLAGS` environment variable:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .config(r#"build.rustflags=["-C", "target-feature=+crt-static"]"#)
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("crt-static"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Cargo configuration]: https://doc.rust-lang.org/cargo/reference/config.html#command-line-overrides
+    pub fn config<S>(&mut self, entry: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.configs.push(entry.as_ref().into());
+        self
+    }
+
+    /// Sets when Cargo uses colored output.
+    ///
+    /// The value is passed to Cargo as is, where Cargo accepts `auto`,
+    /// `always`, and `never`. Colors only affect the STDERR stream, e.g. the
+    /// messages of an [`Error::Command`], and not the compiler configuration.
+    ///
+    /// If this method is not used, the environment conventions apply, in
+    /// order of precedence:
+    ///
+    /// 1. If the `CARGO_TERM_COLOR` environment variable is set, Cargo reads
+    ///    it, so nothing is added to the command.
+    /// 2. If the [`NO_COLOR`] environment variable is set to a non-empty
+    ///    value, the `--color never` argument is added.
+    /// 3. Otherwise, Cargo uses its default, which is `auto`, or the
+    ///    `term.color` value of the [Cargo configuration].
+    ///
+    /// An explicit value from this method always takes precedence over the
+    /// environment variables, because Cargo prefers the command line argument
+    /// over the `CARGO_TERM_COLOR` environment variable.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--color <WHEN>` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --color <WHEN> --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// std::env::remove_var("CARGO_TERM_COLOR");
+    /// std::env::set_var("NO_COLOR", "1");
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// assert!(format!("{:?}", builder).contains(r#" "--color" "never" "#));
+    ///
+    /// builder.color("always");
+    /// let command = format!("{:?}", builder);
+    /// assert!(command.contains(r#" "--color" "always" "#));
+    /// assert!(!command.contains("never"));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    ///
+    /// std::env::set_var("CARGO_TERM_COLOR", "auto");
+    /// let builder = CargoRustcPrintCfg::default();
+    /// assert!(!format!("{:?}", builder).contains("--color"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::Command`]: enum.Error.html#variant.Command
+    /// [`NO_COLOR`]: https://no-color.org
+    /// [Cargo configuration]: https://doc.rust-lang.org/cargo/reference/config.html#termcolor
+    pub fn color<S>(&mut self, when: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.color = Some(when.as_ref().into());
+        self
+    }
+
+    /// Sets a hook to customize the `cargo rustc --print cfg` command before it
+    /// is executed.
+    ///
+    /// The hook is called with the assembled command after all of the
+    /// arguments and environment variables from the other options have been
+    /// applied, so it can inspect or modify any of them, e.g. to wrap the
+    /// process in a sandbox or to apply resource limits. The hook is called
+    /// each time the command is assembled, which can be more than once per
+    /// execution, e.g. when falling back to one command per target, and also
+    /// for the `Debug` implementation. Only one hook is used, so calling this
+    /// method again replaces the hook.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .configure_command(|cmd| {
+    ///         cmd.env("CARGO_TERM_COLOR", "never");
+    ///     })
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn configure_command<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&mut Command) + Send + Sync + 'static,
+    {
+        self.command_hook = Some(CommandHook(Arc::new(f)));
+        self
+    }
+
+    /// Sets the crate type passed to the compiler (rustc).
+    ///
+    /// The default is the crate type of the selected package target, which
+    /// is not always the crate type to analyze, e.g. a `proc-macro` or
+    /// `cdylib` library of a workspace member. The `--crate-type` argument is
+    /// passed to the compiler after any [`rustc_args`], before the
+    /// [`edition`], similar to the [`edition`] method.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--crate-type <CRATE_TYPE>` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg -- --crate-type <CRATE_TYPE>
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.crate_type("cdylib");
+    /// assert!(format!("{:?}", builder).ends_with(
+    ///     r#""--print" "cfg" "--" "--crate-type" "cdylib""#
+    /// ));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`rustc_args`]: #method.rustc_args
+    /// [`edition`]: #method.edition
+    pub fn crate_type<S>(&mut self, crate_type: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.crate_type = Some(crate_type.as_ref().into());
+        self
+    }
+
+    /// Sets the Rust edition passed to the compiler (rustc).
+    ///
+    /// The default is the edition of the package's manifest (Cargo.toml).
+    /// Cargo does not have an argument to override the edition, so the
+    /// `--edition` argument is passed to the compiler after any [`rustc_args`]
+    /// instead. The `2015`, `2018`, `2021`, and `2024` editions are supported
+    /// on all channels, where the `2024` edition requires Rust 1.85 or newer.
+    /// A future edition is only supported on the nightly channel, and it also
+    /// requires the `-Z unstable-options` compiler argument, which can be added
+    /// with the [`rustc_args`] method.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--edition <EDITION>` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg -- --edition <EDITION>
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.edition("2021").rustc_args(&["-C", "debug-assertions=off"]);
+    /// assert!(format!("{:?}", builder).ends_with(
+    ///     r#""--print" "cfg" "--" "-C" "debug-assertions=off" "--edition" "2021""#
+    /// ));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`rustc_args`]: #method.rustc_args
+    pub fn edition<S>(&mut self, edition: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.edition = Some(edition.as_ref().into());
+        self
+    }
+
+    /// Returns the target triples that Cargo uses for the command.
+    ///
+    /// This can differ from the requested rustc targets, because Cargo falls
+    /// back to the `build.target` setting of its configuration, e.g. from a
+    /// `.cargo/config.toml` file, the `CARGO_BUILD_TARGET` environment
+    /// variable, or the [`config`] entries, if no rustc targets are specified,
+    /// and then to the host. This is useful to detect when a configuration
+    /// silently changes the targets. The triples are sorted and deduplicated,
+    /// the same as the compiler configurations from the [`execute`] method,
+    /// and the `host-tuple` target, including from the [`include_host`]
+    /// option, is resolved to the host triple.
+    ///
+    /// The `build.target` setting is read with the `cargo config get` command,
+    /// which is nightly-only. The setting is read from the directory of the
+    /// [`manifest_path`], if set, otherwise the current working directory, the
+    /// same as Cargo discovers the configuration files for the `cargo rustc`
+    /// command. This is only a first step towards correlating compiler
+    /// configurations with Cargo's units, i.e. which crates are compiled for
+    /// which target. In particular, a path to a custom target specification
+    /// (JSON) file is returned as-is, and it is relative to the configuration
+    /// file that sets it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// std::env::remove_var("CARGO_BUILD_TARGET");
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .toolchain_version()?
+    ///     .lines()
+    ///     .find_map(|l| l.strip_prefix("host: "))
+    ///     .map(String::from)
+    ///     .expect("Host triple");
+    /// assert_eq!(CargoRustcPrintCfg::default().effective_targets()?, vec![host]);
+    ///
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .config(r#"build.target=["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"]"#)
+    ///     .effective_targets()?;
+    /// assert_eq!(targets, vec!["i686-pc-windows-msvc", "x86_64-pc-windows-msvc"]);
+    ///
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .config(r#"build.target="x86_64-pc-windows-msvc""#)
+    ///     .rustc_target("i686-pc-windows-msvc")
+    ///     .effective_targets()?;
+    /// assert_eq!(targets, vec!["i686-pc-windows-msvc"]);
+    ///
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .config(r#"build.target=["C:\\specs\\my,target.json", "i686-pc-windows-msvc"]"#)
+    ///     .effective_targets()?;
+    /// assert_eq!(targets, vec![r"C:\specs\my,target.json", "i686-pc-windows-msvc"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Cargo reports an unset `build.target` setting as a failure with the exit
+    /// code `101`, which falls back to the host, while any other failure is an
+    /// error:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::os::unix::process::ExitStatusExt;
+    /// use std::process::{Command, ExitStatus, Output};
+    ///
+    /// let stub = |stderr: &'static str| {
+    ///     move |cmd: &mut Command| {
+    ///         Ok(if cmd.get_args().any(|a| a == "-vV") {
+    ///             Output {
+    ///                 status: ExitStatus::from_raw(0),
+    ///                 stdout: b"rustc 1.0.0\nhost: x86_64-unknown-linux-gnu\n".to_vec(),
+    ///                 stderr: Vec::new(),
+    ///             }
+    ///         } else {
+    ///             assert!(cmd.get_args().any(|a| a == "build.target"));
+    ///             Output {
+    ///                 status: ExitStatus::from_raw(101 << 8),
+    ///                 stdout: Vec::new(),
+    ///                 stderr: stderr.as_bytes().to_vec(),
+    ///             }
+    ///         })
+    ///     }
+    /// };
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .runner(stub("error: config value `build.target` is not set\n"))
+    ///     .effective_targets()?;
+    /// assert_eq!(targets, vec!["x86_64-unknown-linux-gnu"]);
+    ///
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .runner(stub("error: could not load Cargo configuration\n"))
+    ///     .effective_targets();
+    /// assert!(matches!(result, Err(Error::Command { .. })));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The configuration files of the package are found from its manifest,
+    /// regardless of the current working directory:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # std::env::remove_var("CARGO_BUILD_TARGET");
+    /// let package = std::env::temp_dir().join(format!("effective-targets-{}", std::process::id()));
+    /// std::fs::create_dir_all(package.join(".cargo"))?;
+    /// std::fs::write(
+    ///     package.join(".cargo").join("config.toml"),
+    ///     "[build]\ntarget = \"i686-pc-windows-msvc\"\n",
+    /// )?;
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .manifest_path(package.join("Cargo.toml"))
+    ///     .effective_targets()?;
+    /// assert_eq!(targets, vec!["i686-pc-windows-msvc"]);
+    /// # std::fs::remove_dir_all(&package)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`config`]: #method.config
+    /// [`execute`]: #method.execute
+    /// [`include_host`]: #method.include_host
+    /// [`manifest_path`]: #method.manifest_path
+    pub fn effective_targets(&self) -> Result<Vec<String>, Error> {
+        let mut targets = self
+            .rustc_targets
+            .iter()
+            .map(|t| t.to_string_lossy().into_owned())
+            .collect::<Vec<String>>();
+        if targets.is_empty() {
+            let mut cmd = self.cargo_command();
+            if let Some(dir) = self
+                .manifest_path
+                .as_deref()
+                .and_then(Path::parent)
+                .filter(|d| !d.as_os_str().is_empty())
+            {
+                cmd.current_dir(dir);
+            }
+            for config in &self.configs {
+                cmd.arg("--config");
+                cmd.arg(config);
+            }
+            cmd.args([
+                "config",
+                "get",
+                "-Z",
+                "unstable-options",
+                "--format",
+                "json-value",
+                "build.target",
+            ]);
+            let output = self.run(&mut cmd)?;
+            if output.status.success() {
+                let stdout = String::from_utf8(output.stdout).map_err(|source| Error::Output {
+                    command: format!("{:?}", cmd),
+                    source,
+                })?;
+                targets = JsonScanner {
+                    input: &stdout,
+                    position: 0,
+                    subject: "`build.target` configuration",
+                }
+                .strings()?;
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let is_unset = output.status.code() == Some(101)
+                    && stderr.contains("`build.target`")
+                    && stderr.contains("not set");
+                if !is_unset {
+                    return Err(self.command_error(&cmd, output));
+                }
+            }
+        } else if self.include_host {
+            targets.push(String::from(HOST_TUPLE));
+        }
+        if targets.is_empty() || targets.iter().any(|t| t == HOST_TUPLE) {
+            let version = self.toolchain_version()?;
+            let host = version
+                .lines()
+                .find_map(|l| l.strip_prefix("host:"))
+                .map(|h| h.trim().to_string())
+                .ok_or_else(|| Error::from("The host triple does not exist"))?;
+            targets.retain(|t| t != HOST_TUPLE);
+            targets.push(host);
+        }
+        targets.sort();
+        targets.dedup();
+        Ok(targets)
+    }
+
+    /// Selects an example of the package by name.
+    ///
+    /// This can be called multiple times to select multiple examples.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--example <NAME>` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --example <NAME> --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .example("demo")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn example<S>(&mut self, name: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.examples.push(name.as_ref().into());
+        self
+    }
+
+    /// Requires the `Cargo.lock` file to be up-to-date and disables network
+    /// access.
+    ///
+    /// This is equivalent to enabling both [`locked`] and Cargo's `--offline`
+    /// option. The default is disabled.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--frozen` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --frozen --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.frozen(true);
+    /// assert!(format!("{:?}", builder).ends_with(r#""--frozen" "--print" "cfg""#));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`locked`]: #method.locked
+    pub fn frozen(&mut self, f: bool) -> &mut Self {
+        self.frozen = f;
+        self
+    }
+
+    /// Enables or disables the hermetic mode, which forbids network access.
+    ///
+    /// The default is disabled. If enabled, then:
+    ///
+    /// - The `--frozen` and `--offline` arguments are added, so Cargo does not
+    ///   access the network and the `Cargo.lock` file must be up-to-date.
+    /// - The `CARGO_NET_OFFLINE` environment variable is set to `true`, which
+    ///   overrides any `net.offline` configuration.
+    /// - The `CARGO_HTTP_PROXY`, `HTTP_PROXY`, `HTTPS_PROXY`, and `ALL_PROXY`
+    ///   environment variables, and their lowercase variants, are removed
+    ///   from the environment of the command.
+    /// - If the command fails and Cargo reports that it needed the network,
+    ///   then a [`Error::Generic`] error explaining that network access is
+    ///   forbidden is returned instead of the [`Error::Command`] error.
+    ///
+    /// Compared to only using the `--offline` argument, this also fails if the
+    /// `Cargo.lock` file must be updated and it is not affected by the
+    /// environment of the current process. However, it does not sandbox the
+    /// command, e.g. a custom [`program`] or a rustc wrapper configured for
+    /// Cargo could still access the network.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--frozen` and `--offline` arguments to yield:
+    ///
+    /// ```text
+    /// cargo rustc --frozen --offline --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .hermetic(true)
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// If Cargo needs the network, which is simulated with a stub for the
+    /// `cargo` command:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let stub = std::env::temp_dir().join(format!("cargo-offline-stub-{}", std::process::id()));
+    /// # std::fs::write(
+    /// #     &stub,
+    /// #     "#!/bin/sh\necho 'error: failed to download, but --offline was specified' >&2\nexit 101\n",
+    /// # )?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .program(&stub)
+    ///     .hermetic(true)
+    ///     .execute();
+    /// match result {
+    ///     Err(Error::Generic(message)) => assert!(message.contains("network access")),
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    /// # std::fs::remove_file(&stub)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::Generic`]: enum.Error.html#variant.Generic
+    /// [`Error::Command`]: enum.Error.html#variant.Command
+    /// [`program`]: #method.program
+    pub fn hermetic(&mut self, h: bool) -> &mut Self {
+        self.hermetic = h;
+        self
+    }
+
+    /// Ignores the `rust-version` specification of the package.
+    ///
+    /// Cargo refuses to build a package if its `rust-version` in the manifest
+    /// (Cargo.toml) is newer than the toolchain, but the check is usually
+    /// irrelevant when only the compiler configurations are printed. Enable
+    /// this to probe such a package with an older toolchain. The default is
+    /// disabled.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--ignore-rust-version` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --ignore-rust-version --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.ignore_rust_version(true);
+    /// assert!(format!("{:?}", builder).ends_with(r#""--ignore-rust-version" "--print" "cfg""#));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn ignore_rust_version(&mut self, i: bool) -> &mut Self {
+        self.ignore_rust_version = i;
+        self
+    }
+
+    /// Includes the compiler configuration for the host when rustc targets are
+    /// specified.
+    ///
+    /// The default is disabled. If no rustc targets are specified, then the
+    /// compiler configuration for the host is the only one returned and this
+    /// has no effect. Otherwise, the host's compiler configuration is always
+    /// the _first_ item returned from [`execute`], followed by the compiler
+    /// configurations for the specified targets sorted by target triple. If
+    /// the host is also one of the specified targets, its compiler
+    /// configuration is only returned once.
+    ///
+    /// The host triple is determined within the same command invocation by
+    /// adding the `host-tuple` target and the `--print host-tuple` rustc
+    /// argument, which yields:
+    ///
+    /// ```text
+    /// cargo rustc --target host-tuple --target <RUSTC_TARGET> --print cfg -- --print host-tuple
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .include_host(true)
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .execute()?;
+    /// assert_eq!(targets.len(), 3);
+    /// assert_eq!(targets[0], cargo_rustc_cfg::host()?);
+    /// assert_eq!(targets[1].target_env(), Some("gnu"));
+    /// assert_eq!(targets[2].target_env(), Some("msvc"));
+    ///
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .include_host(true)
+    ///     .rustc_target("i686-pc-windows-msvc")
+    ///     .rustc_args(&["--print", "sysroot"])
+    ///     .execute_targets()?;
+    /// assert_eq!(targets.len(), 2);
+    /// assert_eq!(targets[0], cargo_rustc_cfg::host()?);
+    /// assert_eq!(targets[1].target_env(), Some("msvc"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub fn include_host(&mut self, i: bool) -> &mut Self {
+        self.include_host = i;
+        self
+    }
+
+    /// Selects the library of the package.
+    ///
+    /// For procedural macro crates, the library is compiled for the host, so
+    /// selecting the library is essential to determine the relevant compiler
+    /// configuration.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--lib` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --lib --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .lib()
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lib(&mut self) -> &mut Self {
+        self.lib = true;
+        self
+    }
+
+    /// Requires the `Cargo.lock` file to be up-to-date.
+    ///
+    /// Even the `cargo rustc --print cfg` command can update the `Cargo.lock`
+    /// file, so this should be enabled for reproducible, or hermetic, builds.
+    /// Cargo fails instead of updating the `Cargo.lock` file if this is
+    /// enabled. The default is disabled.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--locked` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --locked --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.locked(true);
+    /// assert!(format!("{:?}", builder).ends_with(r#""--locked" "--print" "cfg""#));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn locked(&mut self, l: bool) -> &mut Self {
+        self.locked = l;
+        self
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml) to determine the
+    /// compiler configuration.
+    ///
+    /// The default assumes the current working directory (CWD) contains the
+    /// package's manifest, i.e. at the root directory of the Cargo project. Use
+    /// this method to override this default and determine the compiler
+    /// configuration for a Cargo-based project outside of the CWD.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--manifest-path` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc  --manifest-path <PATH> --print cfg
+    /// ```
+    ///
+    /// where `<PATH>` is replaced with a path to a package's manifest
+    /// (Cargo.toml).
+    ///
+    /// A relative path is resolved by Cargo against the CWD of the current
+    /// process, because the command is always executed from the CWD. This is
+    /// also where Cargo discovers its configuration, e.g.
+    /// `.cargo/config.toml`, so the configuration of the project containing
+    /// the manifest is _not_ used unless the CWD is within the project. Use
+    /// an absolute path to avoid ambiguity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let relative = CargoRustcPrintCfg::default()
+    ///     .manifest_path("Cargo.toml")
+    ///     .execute()?;
+    /// let absolute = CargoRustcPrintCfg::default()
+    ///     .manifest_path(std::env::current_dir()?.join("Cargo.toml"))
+    ///     .execute()?;
+    /// assert_eq!(relative, absolute);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn manifest_path<P>(&mut self, p: P) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.manifest_path = Some(p.into());
+        self
+    }
+
+    /// Limits the content of the STDERR stream kept in an error to the last
+    /// lines.
+    ///
+    /// The default is to keep all of the content. If a command fails, the
+    /// content of its STDERR stream is part of the [`Error::Command`] error
+    /// and its message, which can flood logs. The last lines usually contain
+    /// the actionable message, so only the last `<LINES>` lines are kept if
+    /// this option is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .max_stderr_lines(1)
+    ///     .rustc_target("not-a-real-target")
+    ///     .execute();
+    /// match result {
+    ///     Err(Error::Command { output, .. }) => {
+    ///         assert_eq!(String::from_utf8_lossy(&output.stderr).lines().count(), 1)
+    ///     }
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    /// ```
+    ///
+    /// [`Error::Command`]: enum.Error.html#variant.Command
+    pub fn max_stderr_lines(&mut self, lines: usize) -> &mut Self {
+        self.max_stderr_lines = Some(lines);
+        self
+    }
+
+    /// Enables or disables falling back to one command invocation per target
+    /// when the toolchain does not support the `-Z multitarget` feature.
+    ///
+    /// The default is enabled. If more than one rustc target is specified and
+    /// the toolchain rejects the `-Z multitarget` feature, i.e. the command
+    /// fails and an error message mentions `multitarget`, then the `cargo
+    /// rustc --print cfg` command is executed once for each target instead.
+    /// The results are ordered as if the `-Z multitarget` feature was
+    /// supported, i.e. sorted by target triple. If disabled, an error
+    /// explaining the requirement is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// A toolchain without support for the `-Z multitarget` feature is
+    /// simulated with a stub for the `cargo` command, which fails if the `-Z
+    /// multitarget` feature is used:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    /// # let stub = std::env::temp_dir().join(format!("cargo-stub-{}", std::process::id()));
+    /// # std::fs::write(&stub, format!(
+    /// #     "#!/bin/sh\ncase \"$*\" in *multitarget*) \
+    /// #      echo 'error: unknown `-Z` flag specified: multitarget' >&2; exit 101;; esac\n\
+    /// #      exec {} \"$@\"\n",
+    /// #     cargo
+    /// # ))?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// # std::env::set_var("CARGO", &stub);
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .execute()?;
+    /// assert_eq!(targets[0].get("target_env"), Some("gnu"));
+    /// assert_eq!(targets[1].get("target_env"), Some("msvc"));
+    ///
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .multitarget_fallback(false)
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .execute();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    /// # std::fs::remove_file(&stub)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn multitarget_fallback(&mut self, f: bool) -> &mut Self {
+        self.no_multitarget_fallback = !f;
+        self
+    }
+
+    /// Enables or disables executing the command without a Cargo project.
+    ///
+    /// The `cargo rustc` subcommand requires a package's manifest
+    /// (Cargo.toml), so it fails when executed outside of a Cargo project. The
+    /// default is disabled. If enabled and a [`manifest_path`] is not set, then
+    /// a minimal package is created in a temporary directory, its manifest is
+    /// used for the command, and the temporary directory is removed afterwards.
+    /// This is useful for general-purpose tools that determine the compiler
+    /// configuration for a target from any directory.
+    ///
+    /// The command is still executed from the current working directory, so
+    /// any Cargo configuration, e.g. `.cargo/config.toml`, is discovered as
+    /// usual.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--manifest-path` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --manifest-path <TEMP_DIR>/Cargo.toml --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// std::env::set_current_dir(std::env::temp_dir())?;
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .no_manifest(true)
+    ///     .unstable("unstable-options")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`manifest_path`]: #method.manifest_path
+    pub fn no_manifest(&mut self, n: bool) -> &mut Self {
+        self.no_manifest = n;
+        self
+    }
+
+    /// Enables or disables treating the package as standalone, i.e. outside of
+    /// its workspace.
+    ///
+    /// Cargo resolves the package's manifest (Cargo.toml) from the
+    /// [`manifest_path`], or else by searching the current working directory
+    /// and its parents, and then resolves the workspace root by searching the
+    /// parents of the package's directory for a manifest with a `workspace`
+    /// table. The workspace is loaded even though only the compiler
+    /// configurations are printed, so the command fails if the workspace is
+    /// invalid or does not include the package, e.g. for a nested package that
+    /// is not a member of the enclosing workspace. Note, the profiles of the
+    /// workspace are not applied by Cargo's `--print` flag, so they do not
+    /// change the compiler configurations. The default is disabled.
+    ///
+    /// If enabled, a stub of the package is created in a temporary directory,
+    /// and an empty `workspace` table is appended to the copied manifest, so
+    /// Cargo treats the package as its own workspace root. The stub only
+    /// contains the manifest, the lock file (Cargo.lock) of the package or
+    /// else of the workspace, and the Rust source files of the `src`,
+    /// `examples`, `tests`, and `benches` directories as empty files, which
+    /// Cargo needs to discover the targets of the package. The temporary
+    /// directory is removed afterwards. This has no effect if the package is
+    /// already a workspace root, i.e. its manifest has a `workspace` table or
+    /// a `workspace.*` table, or if the [`no_manifest`] option is enabled.
+    ///
+    /// Path dependencies outside of the package's directory are not
+    /// supported. Neither are fields inherited from the workspace, e.g.
+    /// `version.workspace = true` or `serde = { workspace = true }`, because
+    /// the `workspace.package` and `workspace.dependencies` tables are not
+    /// available outside of the workspace, so a `Generic` error is returned
+    /// instead.
+    ///
+    /// The command is still executed from the current working directory, so
+    /// any Cargo configuration, e.g. `.cargo/config.toml` with the
+    /// `build.target` setting, is discovered as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// # let root = std::env::temp_dir().join(format!("cargo-workspace-{}", std::process::id()));
+    /// # std::fs::create_dir_all(root.join("nested").join("src"))?;
+    /// # std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = []\n")?;
+    /// # std::fs::write(
+    /// #     root.join("nested").join("Cargo.toml"),
+    /// #     "[package]\nname = \"nested\"\nversion = \"0.0.0\"\nedition = \"2018\"\n",
+    /// # )?;
+    /// # std::fs::write(root.join("nested").join("src").join("lib.rs"), "")?;
+    /// let manifest = root.join("nested").join("Cargo.toml");
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .manifest_path(&manifest)
+    ///     .execute();
+    /// assert!(matches!(result, Err(Error::Command { .. })));
+    ///
+    /// let standalone = CargoRustcPrintCfg::default()
+    ///     .manifest_path(&manifest)
+    ///     .no_workspace(true)
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(standalone.has("target_arch"));
+    ///
+    /// # std::fs::create_dir_all(root.join("member").join("src"))?;
+    /// # std::fs::write(
+    /// #     root.join("member").join("Cargo.toml"),
+    /// #     "[package]\nname = \"member\"\nversion.workspace = true\nedition = \"2018\"\n",
+    /// # )?;
+    /// # std::fs::write(root.join("member").join("src").join("lib.rs"), "")?;
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .manifest_path(root.join("member").join("Cargo.toml"))
+    ///     .no_workspace(true)
+    ///     .execute();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    ///
+    /// std::env::set_current_dir(root.join("nested"))?;
+    /// let relative = CargoRustcPrintCfg::default()
+    ///     .unstable("unstable-options")
+    ///     .manifest_path("Cargo.toml")
+    ///     .no_workspace(true)
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(relative, standalone);
+    /// # std::fs::remove_dir_all(&root)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`manifest_path`]: #method.manifest_path
+    /// [`no_manifest`]: #method.no_manifest
+    pub fn no_workspace(&mut self, n: bool) -> &mut Self {
+        self.no_workspace = n;
+        self
+    }
+
+    /// Sets the kind of information printed by the compiler (rustc).
+    ///
+    /// The default is `cfg`. The compiler supports other kinds of information,
+    /// such as `sysroot`, `target-libdir`, and `target-spec-json`. Only the
+    /// `cfg` output can be parsed into compiler configurations, so the
+    /// [`execute_raw`] method must be used to obtain the output for any other
+    /// kind; otherwise, an error is returned.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method replaces `cfg` with `<KIND>` to yield:
+    ///
+    /// ```text
+    /// cargo rustc --print <KIND>
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.print_kind("sysroot");
+    /// let sysroot = builder.execute_raw()?;
+    /// assert!(std::path::Path::new(sysroot.trim()).is_dir());
+    /// assert!(matches!(builder.execute(), Err(Error::Generic(..))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute_raw`]: #method.execute_raw
+    pub fn print_kind<S>(&mut self, kind: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.print_kind = Some(kind.as_ref().into());
+        self
+    }
+
+    /// Sets the program, or driver, used instead of Cargo.
+    ///
+    /// The default is the value of the `CARGO` environment variable if it is
+    /// set; otherwise, the `cargo` application available from the `PATH`
+    /// environment variable. Use this method to invoke Cargo through a wrapper
+    /// application, like `cross` or a build system shim, that accepts the same
+    /// arguments as Cargo. The program takes precedence over the `CARGO`
+    /// environment variable.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method replaces `cargo` with `<PROGRAM>` to yield:
+    ///
+    /// ```text
+    /// <PROGRAM> rustc --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .program(cargo)
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn program<P>(&mut self, p: P) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.program = Some(p.into());
+        self
+    }
+
+    /// Suppresses Cargo's status messages.
+    ///
+    /// Cargo prints status messages, like `Compiling` and `Finished`, to the
+    /// STDERR stream, which can clutter logs when only the compiler
+    /// configuration is wanted. The default is disabled.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--quiet` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --quiet --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.quiet(true);
+    /// assert!(format!("{:?}", builder).ends_with(r#""--quiet" "--print" "cfg""#));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn quiet(&mut self, q: bool) -> &mut Self {
+        self.quiet = q;
+        self
+    }
+
+    /// Sets the number of times the `cargo rustc` command is retried after a
+    /// transient failure.
+    ///
+    /// The default is zero, i.e. no retries. On busy machines, like
+    /// Continuous Integration (CI) servers, the command can fail transiently,
+    /// e.g. from lock contention. Only the following failures are retried:
+    ///
+    /// - An Input/Output (IO) error spawning the command, except if the
+    ///   program is not found or not permitted to be executed.
+    /// - The command is terminated by a signal, i.e. it has no exit code.
+    /// - The STDERR stream mentions lock contention, i.e. `failed to acquire
+    ///   package cache lock`, or a temporarily unavailable or interrupted
+    ///   system resource.
+    ///
+    /// Every other failure, like an unknown target triple or output that
+    /// cannot be parsed, is returned immediately. The delay between retries
+    /// is set with the [`retry_backoff`] method and doubles for each retry.
+    /// The queries, like the [`cargo_version`] method, are never retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .retries(3)
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// A transient failure is retried, which is simulated with a stub for the
+    /// `cargo` command that fails the first time:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use std::time::Duration;
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let dir = std::env::temp_dir().join(format!("cargo-retry-stub-{}", std::process::id()));
+    /// # std::fs::create_dir_all(&dir)?;
+    /// # let stub = dir.join("cargo");
+    /// # std::fs::write(
+    /// #     &stub,
+    /// #     format!(
+    /// #         "#!/bin/sh\necho attempt >> '{0}/log'\nif [ -e '{0}/marker' ]; then echo 'target_arch=\"x86_64\"'; exit 0; fi\n\
+    /// #          touch '{0}/marker'\necho 'error: failed to acquire package cache lock' >&2\nexit 101\n",
+    /// #         dir.display()
+    /// #     ),
+    /// # )?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .program(&stub)
+    ///     .retries(2)
+    ///     .retry_backoff(Duration::from_millis(10))
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(host.target_arch(), Some("x86_64"));
+    /// # assert_eq!(std::fs::read_to_string(dir.join("log"))?.lines().count(), 2);
+    ///
+    /// // Non-transient failures are not retried
+    /// # std::fs::write(&stub, format!("#!/bin/sh\necho attempt >> '{}/log'\necho 'error: bad target' >&2\nexit 101\n", dir.display()))?;
+    /// # std::fs::remove_file(dir.join("log"))?;
+    /// let result = CargoRustcPrintCfg::default().program(&stub).retries(2).execute();
+    /// assert!(matches!(result, Err(Error::Command { .. })));
+    /// # assert_eq!(std::fs::read_to_string(dir.join("log"))?.lines().count(), 1);
+    /// # std::fs::remove_dir_all(&dir)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`retry_backoff`]: #method.retry_backoff
+    /// [`cargo_version`]: #method.cargo_version
+    pub fn retries(&mut self, count: usize) -> &mut Self {
+        self.retries = count;
+        self
+    }
+
+    /// Sets the delay before the first retry of a transient failure.
+    ///
+    /// The default is 100 milliseconds. The delay is doubled for each
+    /// subsequent retry, i.e. an exponential backoff. This has no effect
+    /// unless the number of [`retries`] is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use std::time::Duration;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .retries(3)
+    ///     .retry_backoff(Duration::from_secs(1))
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`retries`]: #method.retries
+    pub fn retry_backoff(&mut self, delay: Duration) -> &mut Self {
+        self.retry_backoff = Some(delay);
+        self
+    }
+
+    /// Sets the runner that executes the commands.
+    ///
+    /// The default is the [`ProcessRunner`], which spawns a process for each
+    /// command. A custom [`CommandRunner`] can substitute the process, e.g.
+    /// to supply canned output in tests that should not depend on an installed
+    /// toolchain. The runner executes every command, including the queries,
+    /// like the [`toolchain_version`] method. Only one runner is used, so
+    /// calling this method again replaces the runner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::os::unix::process::ExitStatusExt;
+    /// use std::process::{ExitStatus, Output};
+    ///
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .runner(|cmd: &mut std::process::Command| {
+    ///         assert!(cmd.get_args().any(|a| a == "cfg"));
+    ///         Ok(Output {
+    ///             status: ExitStatus::from_raw(0),
+    ///             stdout: b"target_arch=\"x86_64\"\ntarget_os=\"linux\"\nunix\n".to_vec(),
+    ///             stderr: Vec::new(),
+    ///         })
+    ///     })
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(host.target_os(), Some("linux"));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ProcessRunner`]: struct.ProcessRunner.html
+    /// [`CommandRunner`]: trait.CommandRunner.html
+    /// [`toolchain_version`]: #method.toolchain_version
+    pub fn runner<R>(&mut self, runner: R) -> &mut Self
+    where
+        R: CommandRunner + 'static,
+    {
+        self.runner = Some(Runner(Arc::new(runner)));
+        self
+    }
+
+    /// Adds arguments to the Cargo command after the `--` flag.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds arguments after `--` to yield:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg -- <RUSTC_ARGS>
+    /// ```
+    ///
+    /// Arguments that change the compiler configurations, like `--cfg`, `-C
+    /// target-feature`, or `-C debug-assertions`, are the intended use. Lint
+    /// levels, like `-D warnings`, do not change the compiler configurations,
+    /// and the `--print cfg` argument does minimal work, i.e. the compiler
+    /// prints the configurations without compiling any code, so most lints are
+    /// never emitted. A lint that is still emitted, e.g. for an unknown lint
+    /// name or a deprecated compiler flag, is turned into an error by a deny
+    /// flag and fails the command, which otherwise would only warn.
+    ///
+    /// # Errors
+    ///
+    /// An error may occur if the `--print cfg` argument is used as a rustc argument.
+    ///
+    /// If the command fails and every error in its output is a lint denied by
+    /// a lint level, e.g. from `-D warnings`, then an [`Error::LintDenied`] is
+    /// returned instead of an [`Error::Command`], so it can be distinguished
+    /// from a genuine inability to print the compiler configurations. If any
+    /// other error is reported, then an [`Error::Command`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .rustc_args(&["-D", "warnings"])
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// If a denied lint fails the command, which is simulated with stubs for
+    /// the `cargo` command:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let stub = std::env::temp_dir().join(format!("cargo-lint-stub-{}", std::process::id()));
+    /// # std::fs::write(
+    /// #     &stub,
+    /// #     "#!/bin/sh\necho 'error: unused variable' >&2\necho '  = note: `-D unused-variables` implied by `-D warnings`' >&2\nexit 101\n",
+    /// # )?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// # let mixed = std::env::temp_dir().join(format!("cargo-lint-mixed-stub-{}", std::process::id()));
+    /// # std::fs::write(
+    /// #     &mixed,
+    /// #     "#!/bin/sh\necho 'error: unused variable' >&2\necho '  = note: `-D unused-variables` implied by `-D warnings`' >&2\necho 'error: unknown codegen option' >&2\nexit 101\n",
+    /// # )?;
+    /// # std::fs::set_permissions(&mixed, std::fs::Permissions::from_mode(0o755))?;
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .program(&stub)
+    ///     .rustc_args(&["-D", "warnings"])
+    ///     .execute();
+    /// match result {
+    ///     Err(Error::LintDenied { output, .. }) => assert_eq!(output.status.code(), Some(101)),
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    ///
+    /// // Any error other than a denied lint is reported as a failed command.
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .program(&mixed)
+    ///     .rustc_args(&["-D", "warnings"])
+    ///     .execute();
+    /// assert!(matches!(result, Err(Error::Command { .. })));
+    /// # std::fs::remove_file(&stub)?;
+    /// # std::fs::remove_file(&mixed)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::LintDenied`]: enum.Error.html#variant.LintDenied
+    /// [`Error::Command`]: enum.Error.html#variant.Command
+    pub fn rustc_args<A, S>(&mut self, a: A) -> &mut Self
+    where
+        A: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.rustc_args = a.into_iter().map(|s| s.as_ref().into()).collect();
+        self
+    }
+
+    /// Sets the sysroot passed to the compiler (rustc).
+    ///
+    /// The default is the sysroot of the toolchain. A custom sysroot, e.g.
+    /// with a custom-built standard library, can change the compiler
+    /// configurations, especially for custom targets. Cargo does not have an
+    /// argument for the sysroot, so the `--sysroot` argument is passed to the
+    /// compiler after any [`rustc_args`], [`crate_type`], and [`edition`]
+    /// instead. This means the same restrictions as the [`rustc_args`] method
+    /// apply, i.e. only one package target can be selected. The [`sysroot`]
+    /// method reports the custom sysroot if this is set.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--sysroot <PATH>` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg -- --sysroot <PATH>
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let sysroot = CargoRustcPrintCfg::default().sysroot()?;
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.rustc_sysroot(&sysroot).rustc_args(&["-C", "debug-assertions=off"]);
+    /// assert!(format!("{:?}", builder).ends_with(&format!(
+    ///     r#""--print" "cfg" "--" "-C" "debug-assertions=off" "--sysroot" {:?}"#,
+    ///     sysroot
+    /// )));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`rustc_args`]: #method.rustc_args
+    /// [`crate_type`]: #method.crate_type
+    /// [`edition`]: #method.edition
+    /// [`sysroot`]: #method.sysroot
+    pub fn rustc_sysroot<P>(&mut self, path: P) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.rustc_sysroot = Some(path.into());
+        self
+    }
+
+    /// Specify a Rust compiler (rustc) target via a target triple.
+    ///
+    /// The `--target` argument is prepended automatically. Please do not include it
+    /// as part of the target triple value.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method would add `--target <RUSTC_TARGET>` to yield:
+    ///
+    /// ```text
+    /// cargo rustc --target <RUSTC_TARGET> --print cfg
+    /// ```
+    ///
+    /// where `<RUSTC_TARGET>` is a target triple from the `rustc --print
+    /// target-list` output.
+    ///
+    /// If more than one rustc target is specified, the `-Z multitarget` option
+    /// will automatically be added to the command invocation.
+    ///
+    /// # Examples
+    ///
+    /// The compiler configuration for the `i686-unknown-linux-gnu` target regardless of the host.
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = CargoRustcPrintCfg::default()
+    ///     .rustc_target("i686-unknown-linux-gnu")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(target.get("target_arch"), Some("x86"));
+    /// assert_eq!(target.get("target_endian"), Some("little"));
+    /// assert_eq!(target.get("target_env"), Some("gnu"));
+    /// assert_eq!(target.get("target_family"), Some("unix"));
+    /// assert_eq!(target.get("target_os"), Some("linux"));
+    /// assert_eq!(target.get("target_pointer_width"), Some("32"));
+    /// assert_eq!(target.get("target_vendor"), Some("unknown"));
+    /// assert_eq!(target.get("unix"), Some("unix"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This method can also be used to specify multiple targets. Note, the
+    /// `multitarget` feature must be enabled. The following example uses
+    /// [`cargo_args`] to enable the multitarget feature (nightly-only).
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .cargo_args(&["-Z", "multitarget"])
+    ///     .rustc_target("i686-pc-windows-msvc")
+    ///     .rustc_target("i686-pc-windows-gnu")
+    ///     .execute()?;
+    /// let gnu = targets.get(0).expect("i686-pc-windows-gnu target");
+    /// let msvc = targets.get(1).expect("i686-pc-windows-msvc target");
+    ///
+    /// assert_eq!(msvc.get("target_arch"), Some("x86"));
+    /// assert_eq!(msvc.get("target_endian"), Some("little"));
+    /// assert_eq!(msvc.get("target_env"), Some("msvc"));
+    /// assert_eq!(msvc.get("target_family"), Some("windows"));
+    /// assert_eq!(msvc.get("target_os"), Some("windows"));
+    /// assert_eq!(msvc.get("target_pointer_width"), Some("32"));
+    /// assert_eq!(msvc.get("target_vendor"), Some("pc"));
+    /// assert_eq!(msvc.get("windows"), Some("windows"));
+    ///
+    /// assert_eq!(gnu.get("target_arch"), Some("x86"));
+    /// assert_eq!(gnu.get("target_endian"), Some("little"));
+    /// assert_eq!(gnu.get("target_env"), Some("gnu"));
+    /// assert_eq!(gnu.get("target_family"), Some("windows"));
+    /// assert_eq!(gnu.get("target_os"), Some("windows"));
+    /// assert_eq!(gnu.get("target_pointer_width"), Some("32"));
+    /// assert_eq!(gnu.get("target_vendor"), Some("pc"));
+    /// assert_eq!(gnu.get("windows"), Some("windows"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`cargo_args`]: #method.cargo_args
+    pub fn rustc_target<T>(&mut self, t: T) -> &mut Self
+    where
+        T: AsRef<OsStr>,
+    {
+        self.rustc_targets.push(t.as_ref().into());
+        self
+    }
+
+    /// Specify multiple Rust compiler (rustc) targets via target triples.
+    ///
+    /// The `--target` argument is prepended automatically. Please do not include it
+    /// as part of the target triple value.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method would add multiple `--target <RUSTC_TARGET>` to yield:
+    ///
+    /// ```text
+    /// cargo rustc --target <RUSTC_TARGET_1> --target <RUSTC_TARGET_2> --print cfg
+    /// ```
+    ///
+    /// where `<RUSTC_TARGET>` is a target triple from the `rustc --print
+    /// target-list` output.
+    ///
+    /// If multiple rustc targets are specified, then the `-Z multitarget`
+    /// option will automatically be added to the command invocation. It can
+    /// also be explicitly added using the [`unstable`] or [`cargo_args`]
+    /// methods or specified in the project's `.cargo/config.toml` file as
+    /// follows:
+    ///
+    /// ```toml
+    /// [unstable]
+    /// multitarget = true
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// Specifying multiple targets and enabling the `-Z multitarget` feature
+    /// with the [`cargo_args`] method (nightly-only):
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .cargo_args(&["-Z", "multitarget"])
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .execute()?;
+    /// let gnu = targets.get(0).expect("i686-pc-windows-gnu target");
+    /// let msvc = targets.get(1).expect("i686-pc-windows-msvc target");
+    ///
+    /// assert_eq!(msvc.get("target_arch"), Some("x86"));
+    /// assert_eq!(msvc.get("target_endian"), Some("little"));
+    /// assert_eq!(msvc.get("target_env"), Some("msvc"));
+    /// assert_eq!(msvc.get("target_family"), Some("windows"));
+    /// assert_eq!(msvc.get("target_os"), Some("windows"));
+    /// assert_eq!(msvc.get("target_pointer_width"), Some("32"));
+    /// assert_eq!(msvc.get("target_vendor"), Some("pc"));
+    /// assert_eq!(msvc.get("windows"), Some("windows"));
+    ///
+    /// assert_eq!(gnu.get("target_arch"), Some("x86"));
+    /// assert_eq!(gnu.get("target_endian"), Some("little"));
+    /// assert_eq!(gnu.get("target_env"), Some("gnu"));
+    /// assert_eq!(gnu.get("target_family"), Some("windows"));
+    /// assert_eq!(gnu.get("target_os"), Some("windows"));
+    /// assert_eq!(gnu.get("target_pointer_width"), Some("32"));
+    /// assert_eq!(gnu.get("target_vendor"), Some("pc"));
+    /// assert_eq!(gnu.get("windows"), Some("windows"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`cargo_args`]: #method.cargo_args
+    /// [`unstable`]: #method.unstable
+    pub fn rustc_targets<T>(&mut self, t: &[T]) -> &mut Self
+    where
+        T: AsRef<OsStr>,
+    {
+        self.rustc_targets.append(
+            &mut t
+                .iter()
+                .map(|t| t.as_ref().into())
+                .collect::<Vec<OsString>>(),
+        );
+        self
+    }
+
+    /// Sets the flags passed to every compiler (rustc) invocation by Cargo.
+    ///
+    /// This is the same as the `RUSTFLAGS` environment variable, which can
+    /// change the compiler configurations, e.g. the `target_feature` values.
+    /// The flags are passed with the `CARGO_ENCODED_RUSTFLAGS` environment
+    /// variable, where the flags are separated by the ASCII Unit Separator
+    /// (`0x1f`) character, so a flag can contain whitespace without being
+    /// split. The `CARGO_ENCODED_RUSTFLAGS` environment variable takes
+    /// precedence over the `RUSTFLAGS` environment variable and the
+    /// `build.rustflags` configuration. Use an empty list to ignore them.
+    ///
+    /// Unlike the [`rustc_args`] method, the flags apply to every target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .rustflags(&["-C", "target-feature=+crt-static"])
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.key_pairs().any(|p| p == ("target_feature", "crt-static")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`rustc_args`]: #method.rustc_args
+    pub fn rustflags<I, S>(&mut self, flags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.rustflags = Some(flags.into_iter().map(|f| f.as_ref().into()).collect());
+        self
+    }
+
+    /// Reads the toolchain from the `RUSTUP_TOOLCHAIN` environment variable
+    /// if a toolchain is not specified with the [`cargo_toolchain`] method.
+    ///
+    /// The default is `false`. Some environments, like Continuous Integration
+    /// (CI) setups, set the `RUSTUP_TOOLCHAIN` environment variable instead of
+    /// using the `+<TOOLCHAIN>` argument. The [`rustup`] proxies already
+    /// honor the environment variable, but only if the command is executed
+    /// through a proxy, i.e. not with a toolchain-specific Cargo from the
+    /// `CARGO` environment variable or the [`program`] method. If enabled,
+    /// the value of the environment variable is passed as the `+<TOOLCHAIN>`
+    /// argument instead, so the toolchain is the same for every command and
+    /// is visible in the command.
+    ///
+    /// An explicit toolchain from the [`cargo_toolchain`] method always takes
+    /// precedence over the environment variable, which is also the precedence
+    /// used by the [`rustup`] proxies. An empty environment variable is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::CargoRustcPrintCfg;
+    /// std::env::remove_var("RUSTUP_TOOLCHAIN");
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.rustup_toolchain(true);
+    /// assert!(!format!("{:?}", builder).contains('+'));
+    ///
+    /// std::env::set_var("RUSTUP_TOOLCHAIN", "nightly");
+    /// assert!(format!("{:?}", builder).contains(r#" "+nightly" "rustc""#));
+    ///
+    /// builder.cargo_toolchain("stable");
+    /// let command = format!("{:?}", builder);
+    /// assert!(command.contains(r#" "+stable" "rustc""#));
+    /// assert!(!command.contains("+nightly"));
+    /// ```
+    ///
+    /// [`cargo_toolchain`]: #method.cargo_toolchain
+    /// [`program`]: #method.program
+    /// [`rustup`]: https://rust-lang.github.io/rustup/
+    pub fn rustup_toolchain(&mut self, yes: bool) -> &mut Self {
+        self.rustup_toolchain = yes;
+        self
+    }
+
+    /// Enables a sanitizer for the compiler (rustc), nightly-only.
+    ///
+    /// A sanitizer changes the compiler configurations, i.e. the
+    /// `sanitize="<KIND>"` configuration is added for each enabled sanitizer,
+    /// so the compiler configurations reflect a sanitized build. This method
+    /// can be used multiple times to enable more than one sanitizer, e.g.
+    /// `address` and `leak`, which are joined into one unstable `-Z
+    /// sanitizer` argument passed to the compiler after any [`rustc_args`],
+    /// similar to the [`edition`] method. The `-Z` arguments are only
+    /// available on the nightly channel and the supported sanitizers depend
+    /// on the target.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `-Z sanitizer=<KIND>` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg -- -Z sanitizer=<KIND>
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::CargoRustcPrintCfg;
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.sanitizer("address").sanitizer("leak");
+    /// assert!(format!("{:?}", builder).ends_with(
+    ///     r#""--print" "cfg" "--" "-Z" "sanitizer=address,leak""#
+    /// ));
+    /// ```
+    ///
+    /// [`rustc_args`]: #method.rustc_args
+    /// [`edition`]: #method.edition
+    pub fn sanitizer<S>(&mut self, kind: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.sanitizers.push(kind.as_ref().into());
+        self
+    }
+
+    /// Sets the subcommand used instead of `rustc`.
+    ///
+    /// The default is `rustc`. This is useful in combination with the
+    /// [`program`] method for wrappers that provide the `cargo rustc`
+    /// functionality through a differently named subcommand.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method replaces `rustc` with `<SUBCOMMAND>` to yield:
+    ///
+    /// ```text
+    /// cargo <SUBCOMMAND> --print cfg
+    /// ```
+    ///
+    /// [`program`]: #method.program
+    pub fn subcommand<S>(&mut self, s: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.subcommand = Some(s.as_ref().into());
+        self
+    }
+
+    /// Executes the `cargo rustc --print sysroot` command and returns the
+    /// path to the sysroot of the compiler (rustc).
+    ///
+    /// The sysroot contains the standard library for the installed targets.
+    /// The command is the same as the `cargo rustc --print cfg` command, i.e.
+    /// the toolchain, targets, and all other options are honored, except the
+    /// [`print_kind`] and [`include_host`] options. If more than one target is
+    /// specified, the sysroot is the same for all of them, so the first path
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let sysroot = CargoRustcPrintCfg::default()
+    ///     .rustc_target("i686-pc-windows-msvc")
+    ///     .sysroot()?;
+    /// assert!(sysroot.join("lib").is_dir());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`print_kind`]: #method.print_kind
+    /// [`include_host`]: #method.include_host
+    pub fn sysroot(&self) -> Result<PathBuf, Error> {
+        let mut builder = self.clone();
+        builder.include_host = false;
+        builder.print_kind(PRINT_SYSROOT);
+        builder
+            .execute_raw()?
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .map(PathBuf::from)
+            .ok_or_else(|| Error::from("The sysroot does not exist"))
+    }
+
+    /// Sets the directory for all generated artifacts and intermediate files.
+    ///
+    /// Invoking the `cargo rustc --print cfg` command can touch the target
+    /// directory and acquire its lock. Use this method to probe the compiler
+    /// configuration with a scratch directory, which avoids lock contention
+    /// and accidental cache invalidation while a build is running concurrently
+    /// with the project's default target directory.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--target-dir` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc --target-dir <PATH> --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target_dir = std::env::temp_dir().join("cargo-rustc-cfg");
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.target_dir(&target_dir);
+    /// assert!(format!("{:?}", builder).ends_with(&format!(
+    ///     r#""--target-dir" {:?} "--print" "cfg""#,
+    ///     target_dir
+    /// )));
+    /// let host = builder.execute()?.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn target_dir<P>(&mut self, p: P) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.target_dir = Some(p.into());
+        self
+    }
+
+    /// Returns the target triples known to the Rust compiler (rustc) for the
+    /// toolchain.
+    ///
+    /// This executes the `rustc --print target-list` command with the same
+    /// toolchain as the `cargo rustc --print cfg` command, i.e. the
+    /// [`cargo_toolchain`] value is honored. The value of the `RUSTC`
+    /// environment variable is used as the program if it is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let triples = CargoRustcPrintCfg::default().target_list()?;
+    /// assert!(triples.iter().any(|t| t == "x86_64-unknown-linux-gnu"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`cargo_toolchain`]: #method.cargo_toolchain
+    pub fn target_list(&self) -> Result<Vec<String>, Error> {
+        let mut cmd = self.rustc_command();
+        cmd.arg("--print");
+        cmd.arg("target-list");
+        let output = self.run(&mut cmd)?;
+        if !output.status.success() {
+            return Err(self.command_error(&cmd, output));
+        }
+        let stdout = String::from_utf8(output.stdout).map_err(|source| Error::Output {
+            command: format!("{:?}", cmd),
+            source,
+        })?;
+        Ok(stdout
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    /// Executes the `cargo rustc --print target-spec-json` command and parses
+    /// selected fields of the target specification.
+    ///
+    /// The command is the same as the `cargo rustc --print cfg` command, i.e.
+    /// the toolchain, target, and all other options are honored, except the
+    /// [`print_kind`] and [`include_host`] options. The `-Z unstable-options`
+    /// compiler argument is added, because the target specification is an
+    /// unstable, nightly-only, feature. An error is returned if more than one
+    /// target is specified.
+    ///
+    /// See the [`TargetSpec`] type for the surfaced fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let spec = CargoRustcPrintCfg::default()
+    ///     .rustc_target("i686-pc-windows-msvc")
+    ///     .target_spec()?;
+    /// assert_eq!(spec.arch, "x86");
+    /// assert_eq!(spec.target_pointer_width, 32);
+    /// assert!(spec.data_layout.starts_with("e-"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`print_kind`]: #method.print_kind
+    /// [`include_host`]: #method.include_host
+    /// [`TargetSpec`]: struct.TargetSpec.html
+    pub fn target_spec(&self) -> Result<TargetSpec, Error> {
+        if self.rustc_targets.len() > 1 {
+            return Err(Error::from(
+                "The target specification can only be printed for one target",
+            ));
+        }
+        let mut builder = self.clone();
+        builder.include_host = false;
+        builder.print_kind(PRINT_TARGET_SPEC_JSON);
+        builder.rustc_args.push(OsString::from("-Z"));
+        builder.rustc_args.push(OsString::from("unstable-options"));
+        builder.execute_raw()?.parse()
+    }
+
+    /// Returns the verbose version information of the Rust compiler (rustc)
+    /// for the toolchain.
+    ///
+    /// This executes the `rustc -vV` command with the same toolchain as the
+    /// `cargo rustc --print cfg` command, i.e. the [`cargo_toolchain`] value is
+    /// honored, and returns its trimmed output. This is useful to record which
+    /// toolchain produced the compiler configurations, e.g. for build
+    /// provenance or as part of a cache key.
+    ///
+    /// The value of the `RUSTC` environment variable is used as the program if
+    /// it is set; otherwise, the `rustc` application available from the `PATH`
+    /// environment variable is used.
+    ///
+    /// For reference, the command is:
+    ///
+    /// ```text
+    /// rustc +<TOOLCHAIN> -vV
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let version = CargoRustcPrintCfg::default()
+    ///     .cargo_toolchain("nightly")
+    ///     .toolchain_version()?;
+    /// assert!(version.starts_with("rustc"));
+    /// assert!(version.contains("release: "));
+    /// assert!(version.contains("-nightly"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`cargo_toolchain`]: #method.cargo_toolchain
+    pub fn toolchain_version(&self) -> Result<String, Error> {
+        let mut cmd = self.rustc_command();
+        cmd.arg("-vV");
+        let output = self.run(&mut cmd)?;
+        if !output.status.success() {
+            return Err(self.command_error(&cmd, output));
+        }
+        let stdout = String::from_utf8(output.stdout).map_err(|source| Error::Output {
+            command: format!("{:?}", cmd),
+            source,
+        })?;
+        Ok(stdout.trim().to_string())
+    }
+
+    /// Returns a copy of the builder for a single rustc target.
+    ///
+    /// The builder methods modify the builder in place, so a base
+    /// configuration can be reused for several related queries by cloning
+    /// it. This clones the builder and replaces any rustc targets with the
+    /// target triple, so the base configuration is left unchanged. Any other
+    /// per-query option can be set on the returned builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut base = CargoRustcPrintCfg::default();
+    /// base.quiet(true).rustc_args(&["-C", "debug-assertions=off"]);
+    ///
+    /// let msvc = base
+    ///     .with_target("i686-pc-windows-msvc")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(msvc.target_env(), Some("msvc"));
+    /// assert!(!msvc.debug_assertions());
+    ///
+    /// let mut gnu = base.with_target("i686-pc-windows-gnu");
+    /// gnu.rustc_args(&["-C", "debug-assertions=on"]);
+    /// let gnu = gnu.execute()?.pop().expect("Compiler configuration");
+    /// assert_eq!(gnu.target_env(), Some("gnu"));
+    /// assert!(gnu.debug_assertions());
+    ///
+    /// assert!(!format!("{:?}", base).contains("--target"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_target<T>(&self, triple: T) -> Self
+    where
+        T: AsRef<OsStr>,
+    {
+        let mut builder = self.clone();
+        builder.rustc_targets.clear();
+        builder.rustc_target(triple);
+        builder
+    }
+
+    /// Adds an unstable, nightly-only, `-Z` flag to the Cargo command.
+    ///
+    /// This can be called multiple times to add multiple flags. The `-Z`
+    /// argument is prepended automatically. Please do not include it as part of
+    /// the flag value.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds `-Z <FLAG>` after the `rustc` subcommand to yield:
+    ///
+    /// ```text
+    /// cargo rustc -Z <FLAG> --print cfg
+    /// ```
+    ///
+    /// The `-Z multitarget` flag is automatically added through this same
+    /// mechanism if more than one rustc target is specified, so it does not
+    /// need to be added explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .unstable("unstable-options")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unstable<S>(&mut self, flag: S) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.unstable_flags.push(flag.as_ref().into());
+        self
+    }
+
+    /// Sets the verbosity of Cargo's output.
+    ///
+    /// The default is zero, i.e. not verbose. A level of one adds the `-v`
+    /// argument and a level of two, or more, adds the `-vv` argument. Cargo
+    /// prints additional diagnostic information, such as the commands that it
+    /// executes, to the STDERR stream, which can be obtained with the
+    /// [`execute_with_warnings`] method. The [`quiet`] option cannot be used
+    /// at the same time.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `-v` argument to yield:
+    ///
+    /// ```text
+    /// cargo rustc -v --print cfg
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut builder = CargoRustcPrintCfg::default();
+    /// builder.verbose(2);
+    /// assert!(format!("{:?}", builder).contains(r#""rustc" "-vv" "--print" "cfg""#));
+    /// let (mut cfgs, _stderr) = builder.execute_with_warnings()?;
+    /// assert!(cfgs.pop().expect("Compiler configuration").has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute_with_warnings`]: #method.execute_with_warnings
+    /// [`quiet`]: #method.quiet
+    pub fn verbose(&mut self, level: u8) -> &mut Self {
+        self.verbose = level;
+        self
+    }
+
+    /// Validates the options and returns a command that can be executed.
+    ///
+    /// Misconfigurations are otherwise only reported by Cargo when the
+    /// command is executed, often with a cryptic error message. The following
+    /// are considered errors:
+    ///
+    /// - An empty toolchain, program, subcommand, print kind, crate type, or
+    ///   rustc target.
+    /// - The same rustc target more than once.
+    /// - More than one rustc target without the [`multitarget_fallback`]
+    ///   option if the toolchain is not a nightly release, because the
+    ///   unstable `multitarget` feature of Cargo is required. Only in this
+    ///   case the `rustc -vV` command is executed to determine the release.
+    /// - Both the [`manifest_path`] and [`no_manifest`] options.
+    /// - Both the [`quiet`] and [`verbose`] options.
+    /// - The [`include_host`] option with a print kind other than `cfg`.
+    /// - The [`rustc_args`], [`crate_type`], [`edition`], [`sanitizer`], or
+    ///   [`rustc_sysroot`] option with more than one selected package target,
+    ///   i.e. the library, binaries, and examples, because Cargo only passes
+    ///   arguments to the compiler for one package target.
+    ///
+    /// The [`execute`] method can still be called directly to skip the
+    /// validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .quiet(true)
+    ///     .build()?
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    ///
+    /// let result = CargoRustcPrintCfg::default().cargo_toolchain("").build();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    ///
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-msvc"])
+    ///     .build();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    ///
+    /// let result = CargoRustcPrintCfg::default().quiet(true).verbose(1).build();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    ///
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .manifest_path("Cargo.toml")
+    ///     .no_manifest(true)
+    ///     .build();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    ///
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .include_host(true)
+    ///     .print_kind("sysroot")
+    ///     .build();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    ///
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .lib()
+    ///     .bin("a")
+    ///     .rustc_args(&["--cfg", "foo"])
+    ///     .build();
+    /// assert!(matches!(result, Err(Error::Generic(..))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// More than one rustc target requires the nightly toolchain without the
+    /// fallback, which is simulated with a stub for the `rustc` command:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let stub = std::env::temp_dir().join(format!("rustc-release-stub-{}", std::process::id()));
+    /// # std::fs::write(&stub, "#!/bin/sh\necho 'release: 1.85.0'\n")?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// # std::env::set_var("RUSTC", &stub);
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .multitarget_fallback(false)
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .build();
+    /// match result {
+    ///     Err(Error::NightlyRequired { release }) => assert_eq!(release, "1.85.0"),
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    ///
+    /// // The fallback executes each target separately on any release.
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .build();
+    /// assert!(result.is_ok());
+    /// # std::fs::remove_file(&stub)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`multitarget_fallback`]: #method.multitarget_fallback
+    /// [`manifest_path`]: #method.manifest_path
+    /// [`no_manifest`]: #method.no_manifest
+    /// [`quiet`]: #method.quiet
+    /// [`verbose`]: #method.verbose
+    /// [`include_host`]: #method.include_host
+    /// [`rustc_args`]: #method.rustc_args
+    /// [`crate_type`]: #method.crate_type
+    /// [`edition`]: #method.edition
+    /// [`sanitizer`]: #method.sanitizer
+    /// [`rustc_sysroot`]: #method.rustc_sysroot
+    /// [`execute`]: #method.execute
+    pub fn build(&self) -> Result<ValidatedCfgCommand, Error> {
+        if self.cargo_toolchain.as_ref().is_some_and(|t| t.is_empty()) {
+            return Err(Error::from("The toolchain cannot be empty"));
+        }
+        if self
+            .program
+            .as_ref()
+            .is_some_and(|p| p.as_os_str().is_empty())
+        {
+            return Err(Error::from("The program cannot be empty"));
+        }
+        if self.subcommand.as_ref().is_some_and(|s| s.is_empty()) {
+            return Err(Error::from("The subcommand cannot be empty"));
+        }
+        if self.print_kind.as_ref().is_some_and(|k| k.is_empty()) {
+            return Err(Error::from("The print kind cannot be empty"));
+        }
+        if self.crate_type.as_ref().is_some_and(|t| t.is_empty()) {
+            return Err(Error::from("The crate type cannot be empty"));
+        }
+        if self.rustc_targets.iter().any(|t| t.is_empty()) {
+            return Err(Error::from("A rustc target cannot be empty"));
+        }
+        let mut unique = HashSet::new();
+        if let Some(target) = self.rustc_targets.iter().find(|t| !unique.insert(*t)) {
+            return Err(Error::Generic(format!(
+                "The '{}' rustc target is used more than once",
+                target.to_string_lossy()
+            )));
+        }
+        if self.no_multitarget_fallback && self.rustc_targets.len() > 1 {
+            if let Some(release) = self.non_nightly_release() {
+                return Err(Error::NightlyRequired { release });
+            }
+        }
+        if self.quiet && self.verbose > 0 {
+            return Err(Error::from(
+                "The quiet and verbose options cannot be used at the same time",
+            ));
+        }
+        if self.no_manifest && self.manifest_path.is_some() {
+            return Err(Error::from(
+                "A manifest path cannot be used without a manifest, remove either option",
+            ));
+        }
+        if self.include_host && self.print_kind.as_deref().is_some_and(|k| k != PRINT_CFG) {
+            return Err(Error::from(
+                "The host can only be included for the `cfg` print kind",
+            ));
+        }
+        let selected = self.bins.len() + self.examples.len() + usize::from(self.lib);
+        if self.has_rustc_args() && selected > 1 {
+            return Err(Error::Generic(format!(
+                "Arguments for the compiler can only be passed to one package target, \
+                 but {} are selected",
+                selected
+            )));
+        }
+        Ok(ValidatedCfgCommand(self.clone()))
+    }
+
+    /// Executes the `cargo rustc` subcommand and returns the value of a
+    /// compiler configuration for each target.
+    ///
+    /// Each item of the returned list is a `(triple, value)` pair, where the
+    /// triple is the target triple of the compiler configuration and the value
+    /// is the same as using the [`RustcTargetCfg::get`] method. The triple is
+    /// `None` if no rustc targets are specified, i.e. only the host's compiler
+    /// configuration is returned. The order is the same as the [`execute`]
+    /// method, but callers do not have to correlate positions with targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let widths = CargoRustcPrintCfg::default()
+    ///     .rustc_targets(&["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"])
+    ///     .get_across_targets("target_pointer_width")?;
+    /// assert_eq!(
+    ///     widths,
+    ///     vec![
+    ///         (Some(String::from("i686-pc-windows-msvc")), Some(String::from("32"))),
+    ///         (Some(String::from("x86_64-pc-windows-msvc")), Some(String::from("64"))),
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RustcTargetCfg::get`]: struct.RustcTargetCfg.html#method.get
+    /// [`execute`]: #method.execute
+    #[allow(clippy::type_complexity)]
+    pub fn get_across_targets(
+        &self,
+        id: &str,
+    ) -> Result<Vec<(Option<String>, Option<String>)>, Error> {
+        Ok(self
+            .execute_labeled()?
+            .0
+            .into_iter()
+            .map(|(triple, cfg)| (triple, cfg.get(id).map(String::from)))
+            .collect())
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options.
+    ///
+    /// For reference, the generic command signature:
+    ///
+    /// ```text
+    /// `cargo +<TOOLCHAIN> rustc <CARGO_ARGS> <RUSTC_TARGETS> --print cfg -- <RUSTC_ARGS>`
+    /// ```
+    ///
+    /// where `<TOOLCHAIN>` is replaced with the [`cargo_toolchain`] value, the
+    /// `<CARGO_ARGS>` is replaced with the [`cargo_args`] value, the
+    /// `<RUSTC_TARGETS>` is appropriately replaced with `--target
+    /// <RUSTC_TARGET> for each specified target from the [`rustc_targets`] or
+    /// [`rustc_target`] methods, and the `<RUSTC_ARGS>` is replaced with the
+    /// [`rustc_args`] value.
+    ///
+    /// One compiler configuration is returned for each target. If more than
+    /// one target is specified, the compiler configurations are sorted by
+    /// target triple and duplicate targets are only returned once, which
+    /// matches the order of the output from Cargo. The host's compiler
+    /// configuration is returned first if the [`include_host`] option is
+    /// enabled. An error is returned if the number of compiler configurations
+    /// in the output does not match the number of targets.
+    ///
+    /// If the command fails because it requires the nightly toolchain, which
+    /// is determined from the `rustc -vV` command only after the failure, then
+    /// the [`Error::NightlyRequired`] error is returned.
+    ///
+    /// The command is executed synchronously, i.e. this method blocks until the
+    /// `cargo` process exits and its output is collected, so a process is
+    /// never left running after this method returns, even if it returns an
+    /// error. There is no asynchronous execution or timeout that could
+    /// abandon a running process. To bound the execution time, use the
+    /// [`configure_command`] method to wrap the command, e.g. with the
+    /// `timeout` utility, which kills the process when the time elapses.
+    ///
+    /// # Examples
+    ///
+    /// If the stable toolchain is used:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .program("cargo")
+    ///     .cargo_toolchain("stable")
+    ///     .execute();
+    /// assert!(matches!(result, Err(Error::NightlyRequired { .. })));
+    /// ```
+    ///
+    /// For a Windows target:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let windows = CargoRustcPrintCfg::default()
+    ///     .rustc_target("x86_64-pc-windows-msvc")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(windows.get("target_arch"), Some("x86_64"));
+    /// assert_eq!(windows.get("target_endian"), Some("little"));
+    /// assert_eq!(windows.get("target_env"), Some("msvc"));
+    /// assert_eq!(windows.get("target_family"), Some("windows"));
+    /// assert_eq!(windows.get("target_os"), Some("windows"));
+    /// assert_eq!(windows.get("target_pointer_width"), Some("64"));
+    /// assert_eq!(windows.get("target_vendor"), Some("pc"));
+    /// assert_eq!(windows.get("windows"), Some("windows"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// For a Linux target:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let linux = CargoRustcPrintCfg::default()
+    ///     .rustc_target("x86_64-unknown-linux-gnu")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(linux.get("target_arch"), Some("x86_64"));
+    /// assert_eq!(linux.get("target_endian"), Some("little"));
+    /// assert_eq!(linux.get("target_env"), Some("gnu"));
+    /// assert_eq!(linux.get("target_family"), Some("unix"));
+    /// assert_eq!(linux.get("target_os"), Some("linux"));
+    /// assert_eq!(linux.get("target_pointer_width"), Some("64"));
+    /// assert_eq!(linux.get("target_vendor"), Some("unknown"));
+    /// assert_eq!(linux.get("unix"), Some("unix"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// If the host is an macOS target:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let macos = CargoRustcPrintCfg::default()
+    ///     .rustc_target("x86_64-apple-darwin")
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(macos.get("target_arch"), Some("x86_64"));
+    /// assert_eq!(macos.get("target_endian"), Some("little"));
+    /// assert_eq!(macos.get("target_env"), Some(""));
+    /// assert_eq!(macos.get("target_family"), Some("unix"));
+    /// assert_eq!(macos.get("target_os"), Some("macos"));
+    /// assert_eq!(macos.get("target_pointer_width"), Some("64"));
+    /// assert_eq!(macos.get("target_vendor"), Some("apple"));
+    /// assert_eq!(macos.get("unix"), Some("unix"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`cargo_toolchain`]: #method.cargo_toolchain
+    /// [`cargo_args`]: #method.cargo_args
+    /// [`rustc_targets`]: #method.rustc_targets
+    /// [`rustc_target`]: #method.rustc_target
+    /// [`rustc_args`]: #method.rustc_args
+    /// [`include_host`]: #method.include_host
+    /// [`Error::NightlyRequired`]: enum.Error.html#variant.NightlyRequired
+    /// [`configure_command`]: #method.configure_command
+    pub fn execute(&self) -> Result<Vec<RustcTargetCfg>, Error> {
+        self.execute_with_warnings().map(|(cfgs, _)| cfgs)
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options
+    /// for every target known to the compiler.
+    ///
+    /// The targets are listed with the [`target_list`] method and replace any
+    /// rustc targets. To bound the number of arguments, and the work, of each
+    /// `cargo rustc` process, the targets are queried in batches, one batch at
+    /// a time, using the `-Z multitarget` feature. The compiler configurations
+    /// are labeled with their target triples, in the order of the target list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let builder = CargoRustcPrintCfg::default();
+    /// let targets = builder.execute_all_targets()?;
+    /// assert_eq!(targets.len(), builder.target_list()?.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`target_list`]: #method.target_list
+    pub fn execute_all_targets(&self) -> Result<Targets, Error> {
+        let triples = self.target_list()?;
+        let mut targets = Vec::new();
+        for batch in triples.chunks(TARGET_BATCH_SIZE) {
+            let mut builder = self.clone();
+            builder.include_host = false;
+            builder.rustc_targets.clear();
+            builder.rustc_targets(batch);
+            targets.append(&mut builder.execute_targets()?.0);
+        }
+        Ok(Targets(targets))
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options
+    /// for the targets the project is configured to build.
+    ///
+    /// The targets are resolved with the [`effective_targets`] method, i.e.
+    /// the `build.target` setting, which can be a list of targets, is read
+    /// with the nightly-only `cargo config get` command from the directory of
+    /// the [`manifest_path`], or the current working directory, the
+    /// `CARGO_BUILD_TARGET` environment variable, and the [`config`] entries,
+    /// or the host is used if it is not set. The
+    /// targets are then passed explicitly and the compiler configurations are
+    /// labeled with their target triples, in sorted order. This is the same
+    /// as the [`execute_targets`] method if rustc targets are specified.
+    ///
+    /// The resolution has the same limitations as the [`effective_targets`]
+    /// method. In particular, a path to a custom target specification (JSON)
+    /// file is relative to the configuration file that sets it, so it only
+    /// resolves if that is the directory of the command.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// std::env::remove_var("CARGO_BUILD_TARGET");
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .config(r#"build.target=["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"]"#)
+    ///     .execute_configured_targets()?;
+    /// assert_eq!(targets.len(), 2);
+    /// let msvc = targets.get("i686-pc-windows-msvc").expect("i686-pc-windows-msvc target");
+    /// assert_eq!(msvc.target_arch(), Some("x86"));
+    /// let msvc = targets.get("x86_64-pc-windows-msvc").expect("x86_64-pc-windows-msvc target");
+    /// assert_eq!(msvc.target_arch(), Some("x86_64"));
+    ///
+    /// let targets = CargoRustcPrintCfg::default().execute_configured_targets()?;
+    /// assert_eq!(targets.len(), 1);
+    /// assert_eq!(targets[0], cargo_rustc_cfg::host()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`effective_targets`]: #method.effective_targets
+    /// [`config`]: #method.config
+    /// [`execute_targets`]: #method.execute_targets
+    /// [`manifest_path`]: #method.manifest_path
+    pub fn execute_configured_targets(&self) -> Result<Targets, Error> {
+        let triples = self.effective_targets()?;
+        let mut builder = self.clone();
+        builder.include_host = false;
+        builder.rustc_targets.clear();
+        builder.rustc_targets(&triples);
+        builder.execute_targets()
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options
+    /// and returns the content of the STDOUT stream without parsing it.
+    ///
+    /// This is useful with the [`print_kind`] method to obtain information
+    /// other than the compiler configurations, but it can be used with the
+    /// default `cfg` kind as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let stdout = CargoRustcPrintCfg::default().execute_raw()?;
+    /// let host = cargo_rustc_cfg::parse_output(&stdout)?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`print_kind`]: #method.print_kind
+    pub fn execute_raw(&self) -> Result<String, Error> {
+        let mut cmd = self.command();
+        let output = self.output_with_retries(&mut cmd)?;
+        if !output.status.success() {
+            return Err(self
+                .nightly_required(&output.stderr)
+                .or_else(|| self.network_required(&output.stderr))
+                .or_else(|| self.lint_denied(&cmd, &output))
+                .unwrap_or_else(|| self.command_error(&cmd, output)));
+        }
+        String::from_utf8(output.stdout).map_err(|source| Error::Output {
+            command: format!("{:?}", cmd),
+            source,
+        })
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options
+    /// and labels each compiler configuration with its target triple.
+    ///
+    /// This is the same as the [`execute`] method, but the compiler
+    /// configurations can be retrieved by target triple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let targets = CargoRustcPrintCfg::default()
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
+    ///     .execute_targets()?;
+    /// let msvc = targets.get("i686-pc-windows-msvc").expect("i686-pc-windows-msvc target");
+    /// assert_eq!(msvc.target_env(), Some("msvc"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub fn execute_targets(&self) -> Result<Targets, Error> {
+        self.execute_labeled().map(|(labeled, _)| Targets(labeled))
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options
+    /// and additional kinds of information printed by the compiler (rustc) in
+    /// the same invocation.
+    ///
+    /// This avoids launching a process for each kind of information, e.g. the
+    /// [`sysroot`] method. The compiler prints the requested kinds in order,
+    /// one line each, before the compiler configurations of each target, so
+    /// the output is partitioned into a [`PrintOutputs`] for each target in
+    /// the same order as the [`execute`] method. Only the kinds printed on a
+    /// single line are supported, i.e. `host-tuple`, `sysroot`, and
+    /// `target-libdir`, while an error is returned for any other kind.
+    ///
+    /// For reference, the default command is:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg
+    /// ```
+    ///
+    /// and this method adds the `--print <KIND>` arguments after `--` to
+    /// yield:
+    ///
+    /// ```text
+    /// cargo rustc --print cfg -- --print <KIND> --print <KIND>
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let builder = CargoRustcPrintCfg::default();
+    /// let host = builder
+    ///     .execute_with_prints(&["sysroot", "target-libdir"])?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(host.cfg.has("target_arch"));
+    /// let sysroot = builder.sysroot()?;
+    /// assert_eq!(host.get("sysroot"), sysroot.to_str());
+    /// assert!(host.get("target-libdir").is_some_and(|d| d.starts_with(sysroot.to_str().unwrap())));
+    ///
+    /// assert!(matches!(
+    ///     builder.execute_with_prints(&["target-list"]),
+    ///     Err(Error::Generic(..))
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The output of the additional kinds is kept verbatim, even if it looks
+    /// like a compiler configuration:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let stub = std::env::temp_dir().join(format!("cargo-prints-stub-{}", std::process::id()));
+    /// # std::fs::write(
+    /// #     &stub,
+    /// #     "#!/bin/sh\necho '/opt/a=\"b/sysroot '\necho 'target_os=\"linux\"'\necho unix\n",
+    /// # )?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// let host = CargoRustcPrintCfg::default()
+    ///     .program(&stub)
+    ///     .execute_with_prints(&["sysroot"])?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert_eq!(host.get("sysroot"), Some("/opt/a=\"b/sysroot "));
+    /// assert_eq!(host.cfg.target_os(), Some("linux"));
+    /// # std::fs::remove_file(&stub)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`sysroot`]: #method.sysroot
+    /// [`PrintOutputs`]: struct.PrintOutputs.html
+    /// [`execute`]: #method.execute
+    pub fn execute_with_prints<S>(&self, kinds: &[S]) -> Result<Vec<PrintOutputs>, Error>
+    where
+        S: AsRef<str>,
+    {
+        if let Some(kind) = kinds
+            .iter()
+            .map(AsRef::as_ref)
+            .find(|k| !SINGLE_LINE_PRINTS.contains(k))
+        {
+            return Err(Error::Generic(format!(
+                "The `--print {}` output cannot be combined with the compiler configurations, \
+                 only the `{}` kinds are supported",
+                kind,
+                SINGLE_LINE_PRINTS.join("`, `")
+            )));
+        }
+        if self.include_host && !self.rustc_targets.is_empty() {
+            return Err(Error::from(
+                "Additional print kinds cannot be combined with the include host option",
+            ));
+        }
+        let mut builder = self.clone();
+        let skipped = builder.rustc_prints();
+        for kind in kinds {
+            builder.rustc_args.push(OsString::from("--print"));
+            builder.rustc_args.push(OsString::from(kind.as_ref()));
+        }
+        let (blocks, _) = builder.execute_blocks(skipped + kinds.len())?;
+        Ok(blocks
+            .into_iter()
+            .map(|(lines, cfg)| PrintOutputs {
+                cfg,
+                prints: kinds
+                    .iter()
+                    .map(|kind| String::from(kind.as_ref()))
+                    .zip(lines.into_iter().skip(skipped))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// This executes the `cargo rustc` subcommand with the appropriate options
+    /// and also returns the content of the STDERR stream.
+    ///
+    /// This is the same as the [`execute`] method, but Cargo may print
+    /// warnings to the STDERR stream even if the command succeeds, which is
+    /// discarded by the [`execute`] method. The STDERR content is returned as
+    /// the second item of the tuple, so it can be surfaced to users, e.g.
+    /// logged, without losing the parsed compiler configurations. If the
+    /// command is executed more than once, i.e. for each target, then the
+    /// STDERR content of each invocation is concatenated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let (mut cfgs, stderr) = CargoRustcPrintCfg::default()
+    ///     .config("unstable.this-key-does-not-exist=true")
+    ///     .execute_with_warnings()?;
+    /// let host = cfgs.pop().expect("Compiler configuration");
+    /// assert!(host.has("target_arch"));
+    /// assert!(stderr.contains("warning"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub fn execute_with_warnings(&self) -> Result<(Vec<RustcTargetCfg>, String), Error> {
+        if self.include_host && !self.rustc_targets.is_empty() {
+            let (labeled, stderr) = self.execute_labeled()?;
+            return Ok((labeled.into_iter().map(|(_, cfg)| cfg).collect(), stderr));
+        }
+        self.execute_command()
+    }
+
+    /// Returns the number of print kinds in the rustc arguments, which the
+    /// compiler prints before the compiler configurations of each target.
+    fn rustc_prints(&self) -> usize {
+        self.rustc_args
+            .iter()
+            .filter(|a| *a == "--print" || a.to_string_lossy().starts_with("--print="))
+            .count()
+    }
+
+    /// Executes the assembled command and parses its output.
+    ///
+    /// The content of the STDERR stream is returned as well.
+    fn execute_command(&self) -> Result<(Vec<RustcTargetCfg>, String), Error> {
+        let (blocks, stderr) = self.execute_blocks(0)?;
+        Ok((blocks.into_iter().map(|(_, cfg)| cfg).collect(), stderr))
+    }
+
+    /// Executes the assembled command and parses its output into the block of
+    /// each target, where the first lines of each block are the verbatim
+    /// output of the additional print kinds.
+    ///
+    /// The content of the STDERR stream is returned as well.
+    fn execute_blocks(&self, prints: usize) -> Result<(Blocks, String), Error> {
+        if let Some(kind) = self.print_kind.as_deref().filter(|k| *k != PRINT_CFG) {
+            return Err(Error::Generic(format!(
+                "The `--print {}` output cannot be parsed into compiler configurations, \
+                 use the `execute_raw` method instead",
+                kind.to_string_lossy()
+            )));
+        }
+        if self.no_manifest && self.manifest_path.is_none() {
+            return self.execute_without_manifest(prints);
+        }
+        if self.no_workspace && !self.no_manifest {
+            return self.execute_without_workspace(prints);
+        }
+        let mut cmd = self.command();
+        let output = self.output_with_retries(&mut cmd)?;
+        if !output.status.success() {
+            if let Some(err) = self
+                .nightly_required(&output.stderr)
+                .or_else(|| self.network_required(&output.stderr))
+                .or_else(|| self.lint_denied(&cmd, &output))
+            {
+                return Err(err);
+            }
+            if self.rustc_targets.len() > 1
+                && String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .any(|l| l.starts_with("error") && l.contains(MULTITARGET))
+            {
+                if self.no_multitarget_fallback {
+                    return Err(Error::Generic(format!(
+                        "Multiple targets require the `-Z {}` feature, which is not supported \
+                         by the toolchain: {}",
+                        MULTITARGET,
+                        String::from_utf8_lossy(&output.stderr)
+                    )));
+                }
+                return self.execute_each_target(prints);
+            }
+            return Err(self.command_error(&cmd, output));
+        }
+        let stdout = String::from_utf8(output.stdout).map_err(|source| Error::Output {
+            command: format!("{:?}", cmd),
+            source,
+        })?;
+        // The `host-tuple` target may be the same as another target, which
+        // cannot be known before the command is executed.
+        let expected = if self.rustc_targets.iter().any(|t| t == HOST_TUPLE) {
+            None
+        } else {
+            let mut triples = self.rustc_targets.iter().collect::<Vec<&OsString>>();
+            triples.sort();
+            triples.dedup();
+            Some(triples.len().max(1))
+        };
+        let cfgs = if prints > 0 {
+            parse_output_with_prints(&stdout, prints)?
+        } else {
+            match expected {
+                Some(count) => parse_output_with_count(&stdout, count)?,
+                None => parse_output(&stdout)?,
+            }
+            .into_iter()
+            .map(|cfg| (Vec::new(), cfg))
+            .collect()
+        };
+        if cfgs.is_empty() {
+            return Err(Error::Generic(format!(
+                "The output from {:?} contains no compiler configurations, STDOUT: '{}', \
+                 STDERR: '{}'",
+                cmd,
+                snippet(&stdout),
+                snippet(&String::from_utf8_lossy(&output.stderr))
+            )));
+        }
+        if let Some(expected) = expected.filter(|e| *e != cfgs.len()) {
+            return Err(Error::TargetCount {
+                expected,
+                found: cfgs.len(),
+                output: stdout,
+            });
+        }
+        Ok((cfgs, String::from_utf8_lossy(&output.stderr).into_owned()))
+    }
+
+    /// Executes the `cargo rustc` subcommand and labels each compiler
+    /// configuration with its target triple.
+    ///
+    /// The label is `None` if no rustc targets are specified. If the host is
+    /// included, its compiler configuration is moved to the front. The content
+    /// of the STDERR stream is returned as well.
+    #[allow(clippy::type_complexity)]
+    fn execute_labeled(&self) -> Result<(Vec<(Option<String>, RustcTargetCfg)>, String), Error> {
+        let mut builder = self.clone();
+        builder.include_host = false;
+        if builder.rustc_targets.is_empty() {
+            let (cfgs, stderr) = builder.execute_command()?;
+            return Ok((cfgs.into_iter().map(|cfg| (None, cfg)).collect(), stderr));
+        }
+        if self.include_host {
+            builder.rustc_target(HOST_TUPLE);
+        }
+        let needs_host_triple = builder.rustc_targets.iter().any(|t| t == HOST_TUPLE);
+        let (cfgs, stderr, host_triple) = if needs_host_triple {
+            // The host triple is printed before the compiler configurations
+            // for each target, after the output of any print kinds of the
+            // rustc arguments.
+            let prints = builder.rustc_prints() + 1;
+            builder.rustc_args.push(OsString::from("--print"));
+            builder.rustc_args.push(OsString::from(HOST_TUPLE));
+            let (blocks, stderr) = builder.execute_blocks(prints)?;
+            let mut host_triple = None;
+            let mut cfgs = Vec::new();
+            for (mut lines, cfg) in blocks {
+                host_triple = lines.pop();
+                cfgs.push(cfg);
+            }
+            if host_triple.is_none() {
+                return Err(Error::from("The host triple does not exist"));
+            }
+            (cfgs, stderr, host_triple)
+        } else {
+            let (cfgs, stderr) = builder.execute_command()?;
+            (cfgs, stderr, None)
+        };
+        let mut triples = builder
+            .rustc_targets
+            .iter()
+            .map(|t| match &host_triple {
+                Some(host_triple) if t == HOST_TUPLE => host_triple.clone(),
+                _ => t.to_string_lossy().into_owned(),
+            })
+            .collect::<Vec<String>>();
+        triples.sort();
+        triples.dedup();
+        if triples.len() != cfgs.len() {
+            return Err(Error::TargetCount {
+                expected: triples.len(),
+                found: cfgs.len(),
+                output: cfgs
+                    .iter()
+                    .map(RustcTargetCfg::to_rustc_format)
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            });
+        }
+        let mut labeled = triples
+            .into_iter()
+            .map(Some)
+            .zip(cfgs)
+            .collect::<Vec<(Option<String>, RustcTargetCfg)>>();
+        if self.include_host {
+            let index = labeled
+                .iter()
+                .position(|(t, _)| t.is_some() && *t == host_triple)
+                .ok_or_else(|| Error::from("The host compiler configuration does not exist"))?;
+            let host = labeled.remove(index);
+            labeled.insert(0, host);
+        }
+        Ok((labeled, stderr))
+    }
+
+    /// Executes the `cargo rustc` subcommand once for each rustc target without
+    /// the `-Z multitarget` feature.
+    ///
+    /// The targets are sorted and deduplicated to match the order of the
+    /// output when the `-Z multitarget` feature is used.
+    fn execute_each_target(&self, prints: usize) -> Result<(Blocks, String), Error> {
+        let mut triples = self.rustc_targets.clone();
+        triples.sort();
+        triples.dedup();
+        let mut targets = Vec::new();
+        let mut stderr = String::new();
+        for triple in triples {
+            let mut single = self.clone();
+            single.rustc_targets = vec![triple];
+            single.unstable_flags.retain(|f| f != MULTITARGET);
+            let (mut cfgs, single_stderr) = single.execute_blocks(prints)?;
+            targets.append(&mut cfgs);
+            stderr.push_str(&single_stderr);
+        }
+        Ok((targets, stderr))
+    }
+
+    /// Executes the `cargo rustc` subcommand with the manifest of a minimal
+    /// package created in a temporary directory.
+    ///
+    /// The temporary directory is removed regardless of the result.
+    fn execute_without_manifest(&self, prints: usize) -> Result<(Blocks, String), Error> {
+        let dir = unique_temp_dir();
+        let result = fs::create_dir_all(dir.join("src"))
+            .and_then(|_| fs::write(dir.join("Cargo.toml"), TEMP_MANIFEST))
+            .and_then(|_| fs::write(dir.join("src").join("lib.rs"), ""))
+            .map_err(Error::from)
+            .and_then(|_| {
+                let mut builder = self.clone();
+                builder.no_manifest = false;
+                builder.manifest_path = Some(dir.join("Cargo.toml"));
+                builder.execute_blocks(prints)
+            });
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    /// Executes the command for a stub of the package that is its own
+    /// workspace root.
+    ///
+    /// The stub is the manifest, the lock file, and the source files of the
+    /// package as empty files in a temporary directory, which is removed
+    /// regardless of the result.
+    fn execute_without_workspace(&self, prints: usize) -> Result<(Blocks, String), Error> {
+        let manifest = match &self.manifest_path {
+            Some(path) => fs::canonicalize(path)?,
+            None => env::current_dir()?
+                .ancestors()
+                .map(|d| d.join("Cargo.toml"))
+                .find(|p| p.is_file())
+                .ok_or_else(|| Error::from("The package's manifest (Cargo.toml) does not exist"))?,
+        };
+        let contents = fs::read_to_string(&manifest)?;
+        let mut builder = self.clone();
+        builder.no_workspace = false;
+        let mut is_root = false;
+        let mut inherits = false;
+        let mut top_level = true;
+        for line in contents.lines() {
+            let line = line
+                .split('#')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>();
+            if line.starts_with('[') {
+                top_level = false;
+                let table = line.trim_matches(|c| c == '[' || c == ']');
+                is_root |= table == "workspace" || table.starts_with("workspace.");
+            } else {
+                is_root |=
+                    top_level && (line.starts_with("workspace.") || line.starts_with("workspace="));
+                inherits |= line.contains("workspace=true");
+            }
+        }
+        if is_root {
+            return builder.execute_blocks(prints);
+        }
+        if inherits {
+            return Err(Error::from(
+                "The package's manifest (Cargo.toml) inherits from the workspace, which is not \
+                 available outside of the workspace",
+            ));
+        }
+        let package = manifest
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| Error::from("The package's directory does not exist"))?;
+        let dir = unique_temp_dir();
+        let result = fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(dir.join("Cargo.toml"), contents + "\n[workspace]\n"))
+            .and_then(|_| {
+                for sources in ["src", "examples", "tests", "benches"] {
+                    if package.join(sources).is_dir() {
+                        stub_sources(&package.join(sources), &dir.join(sources), &mut Vec::new())?;
+                    }
+                }
+                match package
+                    .ancestors()
+                    .map(|d| d.join("Cargo.lock"))
+                    .find(|p| p.is_file())
+                {
+                    Some(lock) => fs::copy(lock, dir.join("Cargo.lock")).map(|_| ()),
+                    None => Ok(()),
+                }
+            })
+            .map_err(Error::from)
+            .and_then(|_| {
+                builder.manifest_path = Some(dir.join("Cargo.toml"));
+                builder.execute_blocks(prints)
+            });
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    /// Assembles the `cargo rustc --print cfg` command from the options.
+    fn command(&self) -> Command {
+        let mut cmd = self.cargo_command();
+        if let Some(rustflags) = &self.rustflags {
+            let mut encoded = OsString::new();
+            for (i, flag) in rustflags.iter().enumerate() {
+                if i > 0 {
+                    encoded.push(RUSTFLAGS_SEPARATOR);
+                }
+                encoded.push(flag);
+            }
+            cmd.env(CARGO_ENCODED_RUSTFLAGS, encoded);
+        }
+        cmd.arg(
+            self.subcommand
+                .as_deref()
+                .unwrap_or_else(|| OsStr::new(RUSTC)),
+        );
+        let mut unstable_flags: Vec<&OsStr> = self
+            .unstable_flags
+            .iter()
+            .map(OsString::as_os_str)
+            .collect();
+        if self.rustc_targets.len() > 1 && !unstable_flags.contains(&OsStr::new(MULTITARGET)) {
+            unstable_flags.push(OsStr::new(MULTITARGET));
+        }
+        for flag in unstable_flags {
+            cmd.arg("-Z");
+            cmd.arg(flag);
+        }
+        cmd.args(&self.cargo_args);
+        if let Some(color) = self.color.as_ref() {
+            cmd.arg("--color");
+            cmd.arg(color);
+        } else if env::var_os(CARGO_TERM_COLOR).is_none()
+            && env::var_os(NO_COLOR).is_some_and(|v| !v.is_empty())
+        {
+            cmd.arg("--color");
+            cmd.arg("never");
+        }
+        if self.frozen || self.hermetic {
+            cmd.arg("--frozen");
+        }
+        if self.hermetic {
+            cmd.arg("--offline");
+            cmd.env(CARGO_NET_OFFLINE, "true");
+            for variable in PROXY_VARIABLES {
+                cmd.env_remove(variable);
+                cmd.env_remove(variable.to_lowercase());
+            }
+        }
+        if self.ignore_rust_version {
+            cmd.arg("--ignore-rust-version");
+        }
+        if self.locked {
+            cmd.arg("--locked");
+        }
+        if self.quiet {
+            cmd.arg("--quiet");
+        }
+        match self.verbose {
+            0 => {}
+            1 => {
+                cmd.arg("-v");
+            }
+            _ => {
+                cmd.arg("-vv");
+            }
+        }
+        if self.lib {
+            cmd.arg("--lib");
+        }
+        for bin in &self.bins {
+            cmd.arg("--bin");
+            cmd.arg(bin);
+        }
+        for example in &self.examples {
+            cmd.arg("--example");
+            cmd.arg(example);
+        }
+        if let Some(manifest_path) = &self.manifest_path {
+            cmd.arg("--manifest-path");
+            cmd.arg(manifest_path);
+        }
+        if let Some(target_dir) = &self.target_dir {
+            cmd.arg("--target-dir");
+            cmd.arg(target_dir);
+        }
+        for config in &self.configs {
+            cmd.arg("--config");
+            cmd.arg(config);
+        }
+        for rustc_target in &self.rustc_targets {
+            cmd.arg("--target");
+            cmd.arg(rustc_target);
+        }
+        cmd.arg("--print");
+        cmd.arg(
+            self.print_kind
+                .as_deref()
+                .unwrap_or_else(|| OsStr::new(PRINT_CFG)),
+        );
+        if self.has_rustc_args() {
+            cmd.arg("--");
+            cmd.args(&self.rustc_args);
+        }
+        if let Some(crate_type) = &self.crate_type {
+            cmd.arg("--crate-type");
+            cmd.arg(crate_type);
+        }
+        if let Some(edition) = &self.edition {
+            cmd.arg("--edition");
+            cmd.arg(edition);
+        }
+        if !self.sanitizers.is_empty() {
+            let mut arg = OsString::from("sanitizer=");
+            for (i, sanitizer) in self.sanitizers.iter().enumerate() {
+                if i > 0 {
+                    arg.push(",");
+                }
+                arg.push(sanitizer);
+            }
+            cmd.arg("-Z");
+            cmd.arg(arg);
+        }
+        if let Some(sysroot) = &self.rustc_sysroot {
+            cmd.arg("--sysroot");
+            cmd.arg(sysroot);
+        }
+        if let Some(hook) = &self.command_hook {
+            (hook.0)(&mut cmd);
+        }
+        cmd
+    }
+
+    /// Returns `true` if any arguments are passed to the compiler after the
+    /// `--` separator.
+    fn has_rustc_args(&self) -> bool {
+        !self.rustc_args.is_empty()
+            || self.crate_type.is_some()
+            || self.edition.is_some()
+            || !self.sanitizers.is_empty()
+            || self.rustc_sysroot.is_some()
+    }
+
+    /// Executes the command with the runner.
+    fn run(&self, cmd: &mut Command) -> std::io::Result<Output> {
+        match &self.runner {
+            Some(runner) => runner.0.run(cmd),
+            None => ProcessRunner.run(cmd),
+        }
+    }
+
+    /// Executes the command and retries it after transient failures.
+    ///
+    /// See the [`retries`] method for the failures that are retried.
+    ///
+    /// [`retries`]: #method.retries
+    fn output_with_retries(&self, cmd: &mut Command) -> Result<Output, Error> {
+        let mut delay = self.retry_backoff.unwrap_or(DEFAULT_RETRY_BACKOFF);
+        let mut attempt = 0;
+        loop {
+            let result = self.run(cmd);
+            let transient = match &result {
+                Ok(output) => {
+                    !output.status.success()
+                        && (output.status.code().is_none() || {
+                            let stderr = String::from_utf8_lossy(&output.stderr);
+                            TRANSIENT_ERRORS.iter().any(|e| stderr.contains(e))
+                        })
+                }
+                Err(err) => !matches!(
+                    err.kind(),
+                    std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied
+                ),
+            };
+            if !transient || attempt >= self.retries {
+                return Ok(result?);
+            }
+            attempt += 1;
+            thread::sleep(delay);
+            delay = delay.saturating_mul(2);
+        }
+    }
+
+    /// Returns an error if a failed command requires the nightly toolchain but
+    /// a different release channel is used.
+    ///
+    /// The release channel is only determined if the STDERR stream of the
+    /// failed command mentions the nightly channel, so successful commands do
+    /// not have the latency of executing the `rustc -vV` command.
+    fn nightly_required(&self, stderr: &[u8]) -> Option<Error> {
+        if !String::from_utf8_lossy(stderr).contains("nightly") {
+            return None;
+        }
+        self.non_nightly_release()
+            .map(|release| Error::NightlyRequired { release })
+    }
+
+    /// Returns the release of the toolchain if it is not a nightly, or
+    /// development, release.
+    ///
+    /// This executes the `rustc -vV` command. If the release cannot be
+    /// determined, then it is assumed to be a nightly release.
+    fn non_nightly_release(&self) -> Option<String> {
+        let version = self.toolchain_version().ok()?;
+        let release = version
+            .lines()
+            .find_map(|l| l.strip_prefix("release:"))
+            .map(str::trim)?;
+        if release.contains("nightly") || release.contains("-dev") {
+            None
+        } else {
+            Some(release.to_string())
+        }
+    }
+
+    /// Returns an error if a failed command needed network access in the
+    /// hermetic mode.
+    fn network_required(&self, stderr: &[u8]) -> Option<Error> {
+        let stderr = String::from_utf8_lossy(stderr);
+        if self.hermetic
+            && stderr
+                .lines()
+                .any(|l| l.contains("--offline") || l.contains("--frozen"))
+        {
+            Some(Error::Generic(format!(
+                "Cargo needs network access, which is forbidden in the hermetic mode: {}",
+                stderr
+            )))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an error if every error of a failed command is a lint denied by
+    /// a lint level, e.g. from the `-D warnings` compiler argument.
+    ///
+    /// A lint is detected by the note of the compiler naming the lint level
+    /// within the diagnostic of the error, which distinguishes it from a
+    /// genuine inability to print the compiler configurations. Diagnostics
+    /// that are not errors, i.e. warnings, and the lines summarizing the
+    /// preceding errors are ignored.
+    fn lint_denied(&self, cmd: &Command, output: &Output) -> Option<Error> {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut errors = Vec::new();
+        let mut in_error = false;
+        for line in stderr.lines() {
+            if line.starts_with("error") {
+                in_error = !ERROR_SUMMARIES.iter().any(|s| line.starts_with(s));
+                if in_error {
+                    errors.push(false);
+                }
+            } else if line.starts_with("warning") {
+                in_error = false;
+            } else if in_error && LINT_LEVEL_NOTES.iter().any(|n| line.contains(n)) {
+                if let Some(lint) = errors.last_mut() {
+                    *lint = true;
+                }
+            }
+        }
+        if !errors.is_empty() && errors.iter().all(|lint| *lint) {
+            Some(Error::LintDenied {
+                command: format!("{:?}", cmd),
+                output: output.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Creates an error for a failed command, which includes the command line.
+    ///
+    /// The content of the STDERR stream is limited to the last lines if the
+    /// [`max_stderr_lines`] option is set.
+    ///
+    /// [`max_stderr_lines`]: #method.max_stderr_lines
+    fn command_error(&self, cmd: &Command, mut output: Output) -> Error {
+        if let Some(max) = self.max_stderr_lines {
+            let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+            let lines = stderr.lines().collect::<Vec<&str>>();
+            if lines.len() > max {
+                let mut kept = lines[lines.len() - max..].join("\n");
+                kept.push('\n');
+                output.stderr = kept.into_bytes();
+            }
+        }
+        Error::Command {
+            command: format!("{:?}", cmd),
+            output,
+        }
+    }
+
+    /// Creates a Cargo command for the program and toolchain.
+    fn cargo_command(&self) -> Command {
+        let mut cmd = Command::new(self.program.clone().unwrap_or_else(|| {
+            env::var(CARGO_VARIABLE)
+                .map(PathBuf::from)
+                .ok()
+                .unwrap_or_else(|| PathBuf::from(CARGO))
+        }));
+        if let Some(toolchain) = self.toolchain() {
+            let mut arg = OsString::from("+");
+            arg.push(toolchain);
+            cmd.arg(arg);
+        }
+        cmd
+    }
+
+    /// Returns the toolchain, either specified explicitly or from the
+    /// `RUSTUP_TOOLCHAIN` environment variable if enabled.
+    fn toolchain(&self) -> Option<OsString> {
+        self.cargo_toolchain.clone().or_else(|| {
+            if self.rustup_toolchain {
+                env::var_os(RUSTUP_TOOLCHAIN_VARIABLE).filter(|t| !t.is_empty())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Creates a Rust compiler (rustc) command for the toolchain.
+    fn rustc_command(&self) -> Command {
+        let mut cmd = Command::new(
+            env::var(RUSTC_VARIABLE)
+                .map(PathBuf::from)
+                .ok()
+                .unwrap_or_else(|| PathBuf::from(RUSTC)),
+        );
+        if let Some(toolchain) = self.toolchain() {
+            let mut arg = OsString::from("+");
+            arg.push(toolchain);
+            cmd.arg(arg);
+        }
+        cmd
+    }
+}
+
+impl fmt::Debug for CargoRustcPrintCfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("CargoRustcPrintCfg")
+                .field("bins", &self.bins)
+                .field("cargo_args", &self.cargo_args)
+                .field("cargo_toolchain", &self.cargo_toolchain)
+                .field("color", &self.color)
+                .field("command_hook", &self.command_hook)
+                .field("configs", &self.configs)
+                .field("crate_type", &self.crate_type)
+                .field("edition", &self.edition)
+                .field("examples", &self.examples)
+                .field("frozen", &self.frozen)
+                .field("hermetic", &self.hermetic)
+                .field("ignore_rust_version", &self.ignore_rust_version)
+                .field("include_host", &self.include_host)
+                .field("lib", &self.lib)
+                .field("locked", &self.locked)
+                .field("manifest_path", &self.manifest_path)
+                .field("max_stderr_lines", &self.max_stderr_lines)
+                .field("no_manifest", &self.no_manifest)
+                .field("no_multitarget_fallback", &self.no_multitarget_fallback)
+                .field("no_workspace", &self.no_workspace)
+                .field("print_kind", &self.print_kind)
+                .field("program", &self.program)
+                .field("quiet", &self.quiet)
+                .field("retries", &self.retries)
+                .field("retry_backoff", &self.retry_backoff)
+                .field("runner", &self.runner)
+                .field("rustc_args", &self.rustc_args)
+                .field("rustc_sysroot", &self.rustc_sysroot)
+                .field("rustc_targets", &self.rustc_targets)
+                .field("rustflags", &self.rustflags)
+                .field("rustup_toolchain", &self.rustup_toolchain)
+                .field("sanitizers", &self.sanitizers)
+                .field("subcommand", &self.subcommand)
+                .field("target_dir", &self.target_dir)
+                .field("unstable_flags", &self.unstable_flags)
+                .field("verbose", &self.verbose)
+                .finish()
+        } else {
+            write!(f, "{:?}", self.command())
+        }
+    }
+}
+
+/// A `cargo rustc --print cfg` command with validated options.
+///
+/// This is returned by the [`build`] method, and it executes the command the
+/// same as the [`CargoRustcPrintCfg`] builder.
+///
+/// [`build`]: struct.CargoRustcPrintCfg.html#method.build
+/// [`CargoRustcPrintCfg`]: struct.CargoRustcPrintCfg.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidatedCfgCommand(CargoRustcPrintCfg);
+
+impl ValidatedCfgCommand {
+    /// See the [`CargoRustcPrintCfg::execute`] method.
+    ///
+    /// [`CargoRustcPrintCfg::execute`]: struct.CargoRustcPrintCfg.html#method.execute
+    pub fn execute(&self) -> Result<Vec<RustcTargetCfg>, Error> {
+        self.0.execute()
+    }
+
+    /// See the [`CargoRustcPrintCfg::execute_raw`] method.
+    ///
+    /// [`CargoRustcPrintCfg::execute_raw`]: struct.CargoRustcPrintCfg.html#method.execute_raw
+    pub fn execute_raw(&self) -> Result<String, Error> {
+        self.0.execute_raw()
+    }
+
+    /// See the [`CargoRustcPrintCfg::execute_targets`] method.
+    ///
+    /// [`CargoRustcPrintCfg::execute_targets`]: struct.CargoRustcPrintCfg.html#method.execute_targets
+    pub fn execute_targets(&self) -> Result<Targets, Error> {
+        self.0.execute_targets()
+    }
+
+    /// See the [`CargoRustcPrintCfg::execute_with_prints`] method.
+    ///
+    /// [`CargoRustcPrintCfg::execute_with_prints`]: struct.CargoRustcPrintCfg.html#method.execute_with_prints
+    pub fn execute_with_prints<S>(&self, kinds: &[S]) -> Result<Vec<PrintOutputs>, Error>
+    where
+        S: AsRef<str>,
+    {
+        self.0.execute_with_prints(kinds)
+    }
+
+    /// See the [`CargoRustcPrintCfg::execute_with_warnings`] method.
+    ///
+    /// [`CargoRustcPrintCfg::execute_with_warnings`]: struct.CargoRustcPrintCfg.html#method.execute_with_warnings
+    pub fn execute_with_warnings(&self) -> Result<(Vec<RustcTargetCfg>, String), Error> {
+        self.0.execute_with_warnings()
+    }
+}
+
+/// A hook to customize a command before it is executed.
+///
+/// This is set with the [`configure_command`] method. Hooks are only equal if
+/// they are the same instance.
+///
+/// [`configure_command`]: struct.CargoRustcPrintCfg.html#method.configure_command
+#[derive(Clone)]
+struct CommandHook(Arc<dyn Fn(&mut Command) + Send + Sync>);
+
+impl fmt::Debug for CommandHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CommandHook")
+    }
+}
+
+impl PartialEq for CommandHook {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A runner that executes the commands of the [`CargoRustcPrintCfg`] builder.
+///
+/// The runner is set with the [`runner`] method. It is implemented for
+/// closures with the same signature as the [`run`] method, so canned output
+/// can be supplied without defining a type.
+///
+/// [`CargoRustcPrintCfg`]: struct.CargoRustcPrintCfg.html
+/// [`runner`]: struct.CargoRustcPrintCfg.html#method.runner
+/// [`run`]: #tymethod.run
+pub trait CommandRunner: Send + Sync {
+    /// Executes the command to completion and collects its output.
+    ///
+    /// This is equivalent to the `output` method of the command.
+    fn run(&self, command: &mut Command) -> std::io::Result<Output>;
+}
+
+impl<F> CommandRunner for F
+where
+    F: Fn(&mut Command) -> std::io::Result<Output> + Send + Sync,
+{
+    fn run(&self, command: &mut Command) -> std::io::Result<Output> {
+        self(command)
+    }
+}
+
+/// The default runner, which spawns a process for each command.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{CommandRunner, ProcessRunner};
+/// # fn main() -> std::io::Result<()> {
+/// let output = ProcessRunner.run(std::process::Command::new("rustc").arg("--version"))?;
+/// assert!(output.status.success());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProcessRunner;
+
+impl CommandRunner for ProcessRunner {
+    fn run(&self, command: &mut Command) -> std::io::Result<Output> {
+        command.output()
+    }
+}
+
+/// A shared custom runner.
+///
+/// This is set with the [`runner`] method. Runners are only equal if they are
+/// the same instance.
+///
+/// [`runner`]: struct.CargoRustcPrintCfg.html#method.runner
+#[derive(Clone)]
+struct Runner(Arc<dyn CommandRunner>);
+
+impl fmt::Debug for Runner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Runner")
+    }
+}
+
+impl PartialEq for Runner {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// The compiler (rustc) configurations for the host and a target.
+///
+/// This is returned by the [`host_and_target`] function.
+///
+/// [`host_and_target`]: fn.host_and_target.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostAndTarget {
+    /// The compiler configurations for the host, which apply to build scripts
+    /// and procedural macros, except for the flags described by the
+    /// [`host_for_build`] function.
+    ///
+    /// [`host_for_build`]: fn.host_for_build.html
+    pub host: RustcTargetCfg,
+    /// The compiler configurations for the requested target.
+    pub target: RustcTargetCfg,
+}
+
+/// The compiler (rustc) configurations and additional kinds of information
+/// printed for a target in the same invocation.
+///
+/// This is returned by the [`execute_with_prints`] method.
+///
+/// [`execute_with_prints`]: struct.CargoRustcPrintCfg.html#method.execute_with_prints
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrintOutputs {
+    /// The compiler configurations for the target.
+    pub cfg: RustcTargetCfg,
+    /// The output of each additional kind of information, labeled by kind, in
+    /// the requested order.
+    pub prints: Vec<(String, String)>,
+}
+
+impl PrintOutputs {
+    /// Returns the output of an additional kind of information, e.g.
+    /// `sysroot`.
+    ///
+    /// This will return `None` if the kind was not requested.
+    pub fn get(&self, kind: &str) -> Option<&str> {
+        self.prints
+            .iter()
+            .find(|(k, _)| k == kind)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl HostAndTarget {
+    /// Returns `true` if the target differs from the host, i.e. the build is
+    /// cross-compiling.
+    ///
+    /// See the [`RustcTargetCfg::is_cross_for`] method for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let cfgs = cargo_rustc_cfg::host_and_target("thumbv7em-none-eabihf")?;
+    /// assert!(cfgs.is_cross());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RustcTargetCfg::is_cross_for`]: struct.RustcTargetCfg.html#method.is_cross_for
+    pub fn is_cross(&self) -> bool {
+        self.target.is_cross_for(&self.host)
+    }
+}
+
+/// The compiler (rustc) configurations for multiple targets labeled by target
+/// triple.
+///
+/// The compiler configurations are ordered the same as the output from the
+/// [`execute`] method, i.e. sorted by target triple, but they can also be
+/// retrieved by target triple with the [`get`] method, so the order never has
+/// to be correlated with the requested targets. The label is `None` if no
+/// targets are specified, i.e. for the default target.
+///
+/// This is returned by the [`targets`] function and the [`execute_targets`]
+/// method.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Error;
+/// # fn main() -> std::result::Result<(), Error> {
+/// let targets = cargo_rustc_cfg::targets(&["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"])?;
+/// assert_eq!(targets.len(), 2);
+/// assert_eq!(
+///     targets.get("x86_64-pc-windows-msvc").and_then(|t| t.target_arch()),
+///     Some("x86_64")
+/// );
+/// assert_eq!(targets[0].target_arch(), Some("x86"));
+/// for (triple, target) in targets.iter() {
+///     assert!(triple.is_some());
+///     assert_eq!(target.target_os(), Some("windows"));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`execute`]: struct.CargoRustcPrintCfg.html#method.execute
+/// [`execute_targets`]: struct.CargoRustcPrintCfg.html#method.execute_targets
+/// [`get`]: #method.get
+/// [`targets`]: fn.targets.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Targets(Vec<(Option<String>, RustcTargetCfg)>);
+
+impl Targets {
+    /// Returns all of the `target_feature` values across all targets.
+    ///
+    /// The set is ordered, so the output is deterministic, e.g. for generated
+    /// documentation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let targets = cargo_rustc_cfg::targets(&["x86_64-unknown-linux-gnu", "aarch64-unknown-linux-gnu"])?;
+    /// let features = targets.feature_union();
+    /// assert!(features.contains("sse2"));
+    /// assert!(features.contains("neon"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn feature_union(&self) -> BTreeSet<String> {
+        self.0
+            .iter()
+            .flat_map(|(_, cfg)| cfg.key_pairs())
+            .filter(|(k, _)| *k == "target_feature")
+            .map(|(_, v)| v.to_string())
+            .collect()
+    }
+
+    /// Returns a reference to the compiler configurations for a target triple.
+    ///
+    /// This will return `None` if the target does not exist.
+    pub fn get(&self, triple: &str) -> Option<&RustcTargetCfg> {
+        self.0
+            .iter()
+            .find(|(t, _)| t.as_deref() == Some(triple))
+            .map(|(_, cfg)| cfg)
+    }
+
+    /// Returns `true` if there are no compiler configurations.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator visiting the target triple and compiler
+    /// configurations of each target.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&str>, &RustcTargetCfg)> + '_ {
+        self.0.iter().map(|(t, cfg)| (t.as_deref(), cfg))
+    }
+
+    /// Returns the number of targets.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Index<usize> for Targets {
+    type Output = RustcTargetCfg;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index].1
+    }
+}
+
+impl IntoIterator for Targets {
+    type Item = (Option<String>, RustcTargetCfg);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// The components of a compiler (rustc) target triple.
+///
+/// A target triple is a list of hyphen-separated components, which is parsed
+/// with the following rules:
+///
+/// - Two components are the architecture and operating system (OS), e.g.
+///   `wasm32-wasip1` or `avr-none`.
+/// - Three components are the architecture, vendor, and OS, e.g.
+///   `x86_64-apple-darwin`, unless the second component is a well-known OS
+///   that is used without a vendor, i.e. `linux`, `none`, `nuttx`, `rtems`,
+///   or a `wasip` version, in which case the components are the
+///   architecture, OS, and environment, e.g. `aarch64-linux-android` or
+///   `thumbv7em-none-eabihf`.
+/// - Four or more components are the architecture, vendor, OS, and
+///   environment, e.g. `x86_64-unknown-linux-gnu`, where any additional
+///   components are part of the environment.
+///
+/// An error is returned for fewer than two components or an empty component.
+/// The components are not validated against the known targets.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{Error, TargetTriple};
+/// # fn main() -> std::result::Result<(), Error> {
+/// let linux = "x86_64-unknown-linux-gnu".parse::<TargetTriple>()?;
+/// assert_eq!(linux.arch, "x86_64");
+/// assert_eq!(linux.vendor.as_deref(), Some("unknown"));
+/// assert_eq!(linux.os, "linux");
+/// assert_eq!(linux.env.as_deref(), Some("gnu"));
+///
+/// let macos = "aarch64-apple-darwin".parse::<TargetTriple>()?;
+/// assert_eq!(macos.vendor.as_deref(), Some("apple"));
+/// assert_eq!(macos.os, "darwin");
+/// assert_eq!(macos.env, None);
+///
+/// let android = "aarch64-linux-android".parse::<TargetTriple>()?;
+/// assert_eq!(android.vendor, None);
+/// assert_eq!(android.os, "linux");
+/// assert_eq!(android.env.as_deref(), Some("android"));
+///
+/// let wasi = "wasm32-wasip1".parse::<TargetTriple>()?;
+/// assert_eq!(wasi.arch, "wasm32");
+/// assert_eq!(wasi.os, "wasip1");
+/// assert_eq!(wasi.to_string(), "wasm32-wasip1");
+///
+/// assert!("x86_64".parse::<TargetTriple>().is_err());
+/// assert!("x86_64--linux".parse::<TargetTriple>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TargetTriple {
+    /// The architecture, e.g. `x86_64`.
+    pub arch: String,
+    /// The vendor, e.g. `unknown` or `apple`, if it is present.
+    pub vendor: Option<String>,
+    /// The operating system, e.g. `linux` or `none`.
+    pub os: String,
+    /// The environment, or ABI, e.g. `gnu` or `eabihf`, if it is present.
+    pub env: Option<String>,
+}
+
+impl FromStr for TargetTriple {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let components = s.split('-').collect::<Vec<&str>>();
+        if components.len() < 2 || components.iter().any(|c| c.is_empty()) {
+            return Err(Error::Generic(format!(
+                "Could not parse '{}' into a target triple",
+                s
+            )));
+        }
+        let owned = |c: &str| c.to_string();
+        Ok(match components.as_slice() {
+            [arch, os] => Self {
+                arch: owned(arch),
+                vendor: None,
+                os: owned(os),
+                env: None,
+            },
+            [arch, os, env] if VENDORLESS_OSES.contains(os) || os.starts_with(WASIP_PREFIX) => {
+                Self {
+                    arch: owned(arch),
+                    vendor: None,
+                    os: owned(os),
+                    env: Some(owned(env)),
+                }
+            }
+            [arch, vendor, os] => Self {
+                arch: owned(arch),
+                vendor: Some(owned(vendor)),
+                os: owned(os),
+                env: None,
+            },
+            [arch, vendor, os, env @ ..] => Self {
+                arch: owned(arch),
+                vendor: Some(owned(vendor)),
+                os: owned(os),
+                env: Some(env.join("-")),
+            },
+            _ => unreachable!("A target triple has at least two components"),
+        })
+    }
+}
+
+impl fmt::Display for TargetTriple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arch)?;
+        if let Some(vendor) = &self.vendor {
+            write!(f, "-{}", vendor)?;
+        }
+        write!(f, "-{}", self.os)?;
+        if let Some(env) = &self.env {
+            write!(f, "-{}", env)?;
+        }
+        Ok(())
+    }
+}
+
+/// The architecture of a compiler (rustc) target, i.e. the value of the
+/// `target_arch` compiler configuration.
+///
+/// The common architectures have a variant, while any other value is
+/// represented by the `Other` variant, so new architectures do not break
+/// existing code. A value is always converted to the variant for a common
+/// architecture instead of the `Other` variant, so matching is reliable.
+///
+/// This is returned by the [`RustcTargetCfg::arch`] method.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Arch;
+/// assert_eq!(Arch::from("x86_64"), Arch::X86_64);
+/// assert_eq!(Arch::from("bpf"), Arch::Other(String::from("bpf")));
+/// assert_eq!(Arch::Aarch64.to_string(), "aarch64");
+/// ```
+///
+/// [`RustcTargetCfg::arch`]: struct.RustcTargetCfg.html#method.arch
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Arch {
+    /// The 64-bit ARM architecture, i.e. `aarch64`.
+    Aarch64,
+    /// The 32-bit ARM architecture, i.e. `arm`.
+    Arm,
+    /// The AVR architecture, i.e. `avr`.
+    Avr,
+    /// The 64-bit LoongArch architecture, i.e. `loongarch64`.
+    LoongArch64,
+    /// The 32-bit MIPS architecture, i.e. `mips`.
+    Mips,
+    /// The 64-bit MIPS architecture, i.e. `mips64`.
+    Mips64,
+    /// The MSP430 architecture, i.e. `msp430`.
+    Msp430,
+    /// The 64-bit NVIDIA PTX architecture, i.e. `nvptx64`.
+    Nvptx64,
+    /// The 32-bit PowerPC architecture, i.e. `powerpc`.
+    PowerPc,
+    /// The 64-bit PowerPC architecture, i.e. `powerpc64`.
+    PowerPc64,
+    /// The 32-bit RISC-V architecture, i.e. `riscv32`.
+    Riscv32,
+    /// The 64-bit RISC-V architecture, i.e. `riscv64`.
+    Riscv64,
+    /// The IBM Z architecture, i.e. `s390x`.
+    S390x,
+    /// The 64-bit SPARC architecture, i.e. `sparc64`.
+    Sparc64,
+    /// The 32-bit WebAssembly architecture, i.e. `wasm32`.
+    Wasm32,
+    /// The 64-bit WebAssembly architecture, i.e. `wasm64`.
+    Wasm64,
+    /// The 32-bit x86 architecture, i.e. `x86`.
+    X86,
+    /// The 64-bit x86 architecture, i.e. `x86_64`.
+    X86_64,
+    /// Any other architecture.
+    Other(String),
+}
+
+impl Arch {
+    /// Returns the value of the `target_arch` compiler configuration.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Arch::Aarch64 => "aarch64",
+            Arch::Arm => "arm",
+            Arch::Avr => "avr",
+            Arch::LoongArch64 => "loongarch64",
+            Arch::Mips => "mips",
+            Arch::Mips64 => "mips64",
+            Arch::Msp430 => "msp430",
+            Arch::Nvptx64 => "nvptx64",
+            Arch::PowerPc => "powerpc",
+            Arch::PowerPc64 => "powerpc64",
+            Arch::Riscv32 => "riscv32",
+            Arch::Riscv64 => "riscv64",
+            Arch::S390x => "s390x",
+            Arch::Sparc64 => "sparc64",
+            Arch::Wasm32 => "wasm32",
+            Arch::Wasm64 => "wasm64",
+            Arch::X86 => "x86",
+            Arch::X86_64 => "x86_64",
+            Arch::Other(arch) => arch,
+        }
+    }
+}
+
+impl From<&str> for Arch {
+    fn from(s: &str) -> Self {
+        match s {
+            "aarch64" => Arch::Aarch64,
+            "arm" => Arch::Arm,
+            "avr" => Arch::Avr,
+            "loongarch64" => Arch::LoongArch64,
+            "mips" => Arch::Mips,
+            "mips64" => Arch::Mips64,
+            "msp430" => Arch::Msp430,
+            "nvptx64" => Arch::Nvptx64,
+            "powerpc" => Arch::PowerPc,
+            "powerpc64" => Arch::PowerPc64,
+            "riscv32" => Arch::Riscv32,
+            "riscv64" => Arch::Riscv64,
+            "s390x" => Arch::S390x,
+            "sparc64" => Arch::Sparc64,
+            "wasm32" => Arch::Wasm32,
+            "wasm64" => Arch::Wasm64,
+            "x86" => Arch::X86,
+            "x86_64" => Arch::X86_64,
+            other => Arch::Other(String::from(other)),
+        }
+    }
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The operating system of a compiler (rustc) target, i.e. the value of the
+/// `target_os` compiler configuration.
+///
+/// The common operating systems have a variant, while any other value is
+/// represented by the `Other` variant, so new operating systems do not break
+/// existing code. A value is always converted to the variant for a common
+/// operating system instead of the `Other` variant, so matching is reliable.
+///
+/// This is returned by the [`RustcTargetCfg::os`] method.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Os;
+/// assert_eq!(Os::from("linux"), Os::Linux);
+/// assert_eq!(Os::from("none"), Os::None);
+/// assert_eq!(Os::from("hermit"), Os::Other(String::from("hermit")));
+/// assert_eq!(Os::MacOs.to_string(), "macos");
+/// ```
+///
+/// [`RustcTargetCfg::os`]: struct.RustcTargetCfg.html#method.os
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Os {
+    /// The `android` operating system.
+    Android,
+    /// The `dragonfly` operating system.
+    DragonFly,
+    /// The `emscripten` operating system.
+    Emscripten,
+    /// The `freebsd` operating system.
+    FreeBsd,
+    /// The `fuchsia` operating system.
+    Fuchsia,
+    /// The `illumos` operating system.
+    Illumos,
+    /// The `ios` operating system.
+    Ios,
+    /// The `linux` operating system.
+    Linux,
+    /// The `macos` operating system.
+    MacOs,
+    /// The `netbsd` operating system.
+    NetBsd,
+    /// No operating system, i.e. `none`, which is used for bare-metal targets.
+    None,
+    /// The `openbsd` operating system.
+    OpenBsd,
+    /// The `solaris` operating system.
+    Solaris,
+    /// The `uefi` operating system.
+    Uefi,
+    /// An `unknown` operating system, e.g. the `wasm32-unknown-unknown`
+    /// target.
+    Unknown,
+    /// The `wasi` operating system.
+    Wasi,
+    /// The `windows` operating system.
+    Windows,
+    /// Any other operating system.
+    Other(String),
+}
+
+impl Os {
+    /// Returns the value of the `target_os` compiler configuration.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Os::Android => "android",
+            Os::DragonFly => "dragonfly",
+            Os::Emscripten => "emscripten",
+            Os::FreeBsd => "freebsd",
+            Os::Fuchsia => "fuchsia",
+            Os::Illumos => "illumos",
+            Os::Ios => "ios",
+            Os::Linux => "linux",
+            Os::MacOs => "macos",
+            Os::NetBsd => "netbsd",
+            Os::None => "none",
+            Os::OpenBsd => "openbsd",
+            Os::Solaris => "solaris",
+            Os::Uefi => "uefi",
+            Os::Unknown => "unknown",
+            Os::Wasi => "wasi",
+            Os::Windows => "windows",
+            Os::Other(os) => os,
+        }
+    }
+}
+
+impl From<&str> for Os {
+    fn from(s: &str) -> Self {
+        match s {
+            "android" => Os::Android,
+            "dragonfly" => Os::DragonFly,
+            "emscripten" => Os::Emscripten,
+            "freebsd" => Os::FreeBsd,
+            "fuchsia" => Os::Fuchsia,
+            "illumos" => Os::Illumos,
+            "ios" => Os::Ios,
+            "linux" => Os::Linux,
+            "macos" => Os::MacOs,
+            "netbsd" => Os::NetBsd,
+            "none" => Os::None,
+            "openbsd" => Os::OpenBsd,
+            "solaris" => Os::Solaris,
+            "uefi" => Os::Uefi,
+            "unknown" => Os::Unknown,
+            "wasi" => Os::Wasi,
+            "windows" => Os::Windows,
+            other => Os::Other(String::from(other)),
+        }
+    }
+}
+
+impl fmt::Display for Os {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The commonly used compiler (rustc) configurations of a target extracted at
+/// once.
+///
+/// Each field is `None`, or empty, if the corresponding compiler configuration
+/// does not exist.
+///
+/// This is returned by the [`RustcTargetCfg::summary`] method.
+///
+/// [`RustcTargetCfg::summary`]: struct.RustcTargetCfg.html#method.summary
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Summary {
+    /// The value of the `target_arch` compiler configuration.
+    pub arch: Option<Arch>,
+    /// The value of the `target_os` compiler configuration.
+    pub os: Option<Os>,
+    /// The value of the `target_env` compiler configuration.
+    pub env: Option<String>,
+    /// The values of the `target_family` compiler configurations, where a
+    /// target can belong to more than one family, e.g. `unix` and `wasm`.
+    pub families: Vec<String>,
+    /// The value of the `target_pointer_width` compiler configuration as a
+    /// number of bits.
+    pub pointer_width: Option<u32>,
+    /// The value of the `target_endian` compiler configuration.
+    pub endian: Option<String>,
+}
+
+/// Selected fields of the target specification of a compiler (rustc) target.
+///
+/// The target specification is printed as JSON by the `--print
+/// target-spec-json` compiler argument, which is an unstable, nightly-only,
+/// feature. The format of the output is not stable and it may change between
+/// releases of the compiler. Only the following fields are surfaced:
+///
+/// - `llvm-target`
+/// - `arch`
+/// - `data-layout`
+/// - `target-pointer-width`
+/// - `target-c-int-width`
+/// - `c-enum-min-bits`
+/// - `max-atomic-width`
+///
+/// This is returned by the [`target_spec`] method, and it can be parsed from
+/// previously captured output.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{Error, TargetSpec};
+/// # fn main() -> std::result::Result<(), Error> {
+/// let spec = r#"{
+///   "arch": "arm",
+///   "c-enum-min-bits": 8,
+///   "data-layout": "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+///   "llvm-target": "thumbv7em-none-eabihf",
+///   "max-atomic-width": 32,
+///   "supported-sanitizers": ["kcfi"],
+///   "target-pointer-width": 32
+/// }"#
+/// .parse::<TargetSpec>()?;
+/// assert_eq!(spec.llvm_target, "thumbv7em-none-eabihf");
+/// assert_eq!(spec.target_pointer_width, 32);
+/// assert_eq!(spec.target_c_int_width, None);
+/// assert_eq!(spec.c_enum_min_bits, Some(8));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`target_spec`]: struct.CargoRustcPrintCfg.html#method.target_spec
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetSpec {
+    /// The target triple used by LLVM, i.e. the `llvm-target` field.
+    pub llvm_target: String,
+    /// The architecture, i.e. the `arch` field.
+    pub arch: String,
+    /// The LLVM data layout string, i.e. the `data-layout` field, which
+    /// describes the endianness, alignment, and sizes of types.
+    pub data_layout: String,
+    /// The width of a pointer in bits, i.e. the `target-pointer-width` field.
+    pub target_pointer_width: u32,
+    /// The width of the C `int` type in bits, i.e. the `target-c-int-width`
+    /// field, if it is present.
+    pub target_c_int_width: Option<u32>,
+    /// The minimum size of a C enum in bits, i.e. the `c-enum-min-bits` field,
+    /// if it is present.
+    pub c_enum_min_bits: Option<u32>,
+    /// The width of the largest supported atomic integer in bits, i.e. the
+    /// `max-atomic-width` field, if it is present.
+    pub max_atomic_width: Option<u32>,
+}
+
+impl FromStr for TargetSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = JsonScanner {
+            input: s,
+            position: 0,
+            subject: "target specification",
+        }
+        .top_level_scalars()?;
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+        };
+        let required = |name: &str| {
+            field(name).map(String::from).ok_or_else(|| {
+                Error::Generic(format!(
+                    "The target specification does not contain the '{}' field",
+                    name
+                ))
+            })
+        };
+        let number = |name: &str| {
+            field(name)
+                .map(|v| {
+                    v.parse::<u32>().map_err(|_| {
+                        Error::Generic(format!(
+                            "The '{}' field of the target specification is not a number: {}",
+                            name, v
+                        ))
+                    })
+                })
+                .transpose()
+        };
+        Ok(Self {
+            llvm_target: required("llvm-target")?,
+            arch: required("arch")?,
+            data_layout: required("data-layout")?,
+            target_pointer_width: number("target-pointer-width")?.ok_or_else(|| {
+                Error::from(
+                    "The target specification does not contain the 'target-pointer-width' field",
+                )
+            })?,
+            target_c_int_width: number("target-c-int-width")?,
+            c_enum_min_bits: number("c-enum-min-bits")?,
+            max_atomic_width: number("max-atomic-width")?,
+        })
+    }
+}
+
+/// A scanner for the top-level fields of a JSON object.
+///
+/// This is used to parse the [`TargetSpec`] without a dependency on a JSON
+/// library. Only string, number, boolean, and null values are returned, with
+/// strings unescaped and other values as written. Nested objects and arrays
+/// are skipped. It also parses a string or an array of strings, e.g. the
+/// `build.target` configuration value.
+///
+/// [`TargetSpec`]: struct.TargetSpec.html
+struct JsonScanner<'a> {
+    input: &'a str,
+    position: usize,
+    subject: &'a str,
+}
+
+impl JsonScanner<'_> {
+    fn top_level_scalars(&mut self) -> Result<Vec<(String, String)>, Error> {
+        let mut fields = Vec::new();
+        self.expect('{')?;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                break;
+            }
+            let key = self.string()?;
+            self.expect(':')?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some('"') => fields.push((key, self.string()?)),
+                Some('{') | Some('[') => self.skip_nested()?,
+                Some(_) => {
+                    let rest = &self.input[self.position..];
+                    let len = rest
+                        .find(|c: char| c == ',' || c == '}' || c.is_whitespace())
+                        .unwrap_or(rest.len());
+                    if len == 0 {
+                        return Err(self.error("expected a value"));
+                    }
+                    fields.push((key, rest[..len].to_string()));
+                    self.position += len;
+                }
+                None => return Err(self.error("expected a value")),
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => break,
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+        self.expect('}')?;
+        Ok(fields)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+        let mut value = String::new();
+        let mut chars = self.input[self.position..].chars();
+        while let Some(c) = chars.next() {
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let e = chars
+                        .next()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += e.len_utf8();
+                    match e {
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'u' => {
+                            let hex = chars.by_ref().take(4).collect::<String>();
+                            self.position += hex.len();
+                            let code = u32::from_str_radix(&hex, 16)
+                                .map_err(|_| self.error("invalid unicode escape"))?;
+                            value.push(std::char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        e => value.push(e),
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, Error> {
+        self.skip_whitespace();
+        let values = if self.peek() == Some('[') {
+            self.position += 1;
+            let mut values = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.peek() == Some(']') {
+                    break;
+                }
+                values.push(self.string()?);
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(',') => self.position += 1,
+                    Some(']') => break,
+                    _ => return Err(self.error("expected `,` or `]`")),
+                }
+            }
+            self.expect(']')?;
+            values
+        } else {
+            vec![self.string()?]
+        };
+        self.skip_whitespace();
+        if self.position < self.input.len() {
+            return Err(self.error("expected the end"));
+        }
+        Ok(values)
+    }
+
+    fn skip_nested(&mut self) -> Result<(), Error> {
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' => {
+                    self.string()?;
+                    continue;
+                }
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth -= 1,
+                _ => {}
+            }
+            self.position += c.len_utf8();
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+        Err(self.error("unterminated object or array"))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", expected)));
+        }
+        self.position += expected.len_utf8();
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Generic(format!(
+            "Could not parse the {}, {} at position {}",
+            self.subject, message, self.position
+        ))
+    }
+}
+
+/// A container for the compiler (rustc) configurations for a specific compiler
+/// target.
+///
+/// The container dereferences to a slice of compiler configurations, so slice
+/// methods are available, too. Note, the [`get`] and [`iter`] methods of this
+/// container take precedence over the slice methods with the same names.
+///
+/// The container can be indexed by position, like a slice, or by identifier
+/// (ID), which is the same as the [`get`] method, but it panics if the
+/// compiler configuration does not exist, like indexing a `HashMap`. Use the
+/// [`get`] method as the non-panicking alternative.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+/// # fn main() -> std::result::Result<(), Error> {
+/// let target = "debug_assertions\ntarget_os=\"linux\"\nunix".parse::<RustcTargetCfg>()?;
+/// assert_eq!(target.len(), 3);
+/// assert_eq!(target.first(), Some(&Cfg::Name(String::from("debug_assertions"))));
+/// assert_eq!(target.last(), Some(&Cfg::Name(String::from("unix"))));
+///
+/// fn count<C: AsRef<[Cfg]>>(cfgs: C) -> usize {
+///     cfgs.as_ref().len()
+/// }
+/// assert_eq!(count(&target), 3);
+///
+/// let target = RustcTargetCfg::from(vec![
+///     Cfg::Name(String::from("unix")),
+///     Cfg::KeyPair(String::from("target_os"), String::from("linux")),
+/// ]);
+/// assert_eq!(target.target_os(), Some("linux"));
+/// assert_eq!(&target["target_os"], "linux");
+/// assert_eq!(&target["unix"], "unix");
+/// assert_eq!(target[0], Cfg::Name(String::from("unix")));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Indexing by a missing identifier panics:
+///
+/// ```should_panic
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+/// # fn main() -> std::result::Result<(), Error> {
+/// let target = "unix".parse::<RustcTargetCfg>()?;
+/// let _ = &target["target_os"];
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`get`]: #method.get
+/// [`iter`]: #method.iter
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RustcTargetCfg(Vec<Cfg>);
+
+impl RustcTargetCfg {
+    /// Returns an iterator visiting all compiler configurations for the
+    /// compiler (rustc) target.
+    ///
+    /// This is the same as iterating over a reference to the container.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_os=\"linux\"\nunix".parse::<RustcTargetCfg>()?;
+    /// let mut names = Vec::new();
+    /// for cfg in &target {
+    ///     names.extend(cfg.name());
+    /// }
+    /// assert_eq!(names, vec!["unix"]);
+    /// assert!(target.iter().eq(&target));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, Cfg> {
+        self.0.iter()
+    }
+
+    /// Returns the compiler configurations as a slice.
+    pub fn as_slice(&self) -> &[Cfg] {
+        &self.0
+    }
+
+    /// Consumes the compiler target and returns its compiler configurations.
+    ///
+    /// This does not allocate, unlike collecting the items of the
+    /// `IntoIterator` implementation, and it is the inverse of the
+    /// `From<Vec<Cfg>>` implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let cfgs = vec![
+    ///     Cfg::Name(String::from("unix")),
+    ///     Cfg::KeyPair(String::from("target_os"), String::from("linux")),
+    /// ];
+    /// let target = RustcTargetCfg::from(cfgs.clone());
+    /// assert_eq!(target.as_slice(), cfgs.as_slice());
+    /// assert_eq!(target.clone().into_inner(), cfgs);
+    /// assert_eq!(Vec::from(target), cfgs);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_inner(self) -> Vec<Cfg> {
+        self.0
+    }
+
+    /// Returns an iterator visiting only the key-value pair compiler
+    /// configurations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "debug_assertions\ntarget_os=\"linux\"\nunix".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.key_pairs().collect::<Vec<_>>(), vec![("target_os", "linux")]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn key_pairs(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.0.iter().filter_map(Cfg::key_pair)
+    }
+
+    /// Returns an iterator visiting only the name compiler configurations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "debug_assertions\ntarget_os=\"linux\"\nunix".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.names().collect::<Vec<_>>(), vec!["debug_assertions", "unix"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.iter().filter_map(Cfg::name)
+    }
+
+    /// Returns the compiler configurations that satisfy a predicate.
+    ///
+    /// This is the same as `iter().filter(f).collect()`, and it is useful for
+    /// selections that are more complex than matching an identifier (ID)
+    /// with the [`get`] method.
+    ///
+    /// # Examples
+    ///
+    /// Select only the key-value pairs with numeric values:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_has_atomic=\"64\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\"\nunix".parse::<RustcTargetCfg>()?;
+    /// let numeric = target.filter(|c| match c {
+    ///     Cfg::KeyPair(_, v) => v.parse::<u64>().is_ok(),
+    ///     Cfg::Name(..) => false,
+    /// });
+    /// assert_eq!(
+    ///     numeric,
+    ///     vec![
+    ///         &Cfg::KeyPair(String::from("target_has_atomic"), String::from("64")),
+    ///         &Cfg::KeyPair(String::from("target_pointer_width"), String::from("64")),
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`get`]: #method.get
+    pub fn filter<F>(&self, f: F) -> Vec<&Cfg>
+    where
+        F: Fn(&Cfg) -> bool,
+    {
+        self.0.iter().filter(|c| f(c)).collect()
+    }
+
+    /// Returns a reference to the compiler configuration value with the
+    /// corresponding identifier (ID).
+    ///
+    /// In the case of a name compiler configuration, the name is the value. If
+    /// the compiler configuration is a key-value pair, the value will be
+    /// returned if the key matches the ID.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.0.iter().find_map(|c| match c {
+            Cfg::Name(n) => {
+                if n == id {
+                    Some(n.as_ref())
+                } else {
+                    None
+                }
+            }
+            Cfg::KeyPair(k, v) => {
+                if k == id {
+                    Some(v.as_ref())
+                } else {
+                    None
+                }
+            }
+        })
+    }
+
+    /// Returns a reference to the identifier (ID) of the first compiler
+    /// configuration with the corresponding value.
+    ///
+    /// This is the reverse of the [`get`] method. In the case of a name
+    /// compiler configuration, the name is the value, so the name is returned
+    /// if it matches the value. If the compiler configuration is a key-value
+    /// pair, the key will be returned if the value matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_arch=\"x86_64\"\ntarget_family=\"unix\"\nunix".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.get_by_value("x86_64"), Some("target_arch"));
+    /// assert_eq!(target.get_by_value("unix"), Some("target_family"));
+    /// let target = "unix\ntarget_family=\"unix\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.get_by_value("unix"), Some("unix"));
+    /// assert_eq!(target.get_by_value("linux"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`get`]: #method.get
+    pub fn get_by_value(&self, value: &str) -> Option<&str> {
+        self.0.iter().find_map(|c| match c {
+            Cfg::Name(n) if n == value => Some(n.as_ref()),
+            Cfg::KeyPair(k, v) if v == value => Some(k.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Returns the compiler configuration value with the corresponding
+    /// identifier (ID), or a default if it does not exist.
+    ///
+    /// This is the same as the [`get`] method, but it avoids repeating the
+    /// fallback at every call site. It is especially useful for the less
+    /// common compiler configurations that only some toolchains or targets
+    /// emit, such as `relocation_model` (e.g. `pic`) and `fmt_debug` on newer
+    /// nightly toolchains, or `panic`. Note, the code model is not emitted as
+    /// a compiler configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "panic=\"abort\"\ntarget_os=\"none\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.get_or_default("panic", "unwind"), "abort");
+    /// assert_eq!(target.get_or_default("relocation_model", "static"), "static");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`get`]: #method.get
+    pub fn get_or_default<'a>(&'a self, id: &str, default: &'a str) -> &'a str {
+        self.get(id).unwrap_or(default)
+    }
+
+    /// Parses the compiler configuration value with the corresponding
+    /// identifier (ID) into a type.
+    ///
+    /// The value is looked up the same as the [`get`] method and parsed with
+    /// the `FromStr` implementation of the type, which generalizes typed
+    /// accessors like the [`pointer_width`] method to any type, e.g. for
+    /// custom configurations with numeric values. This will return `None` if
+    /// the configuration does not exist and `Some` with the parse result
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_os=\"linux\"\ntarget_pointer_width=\"64\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.get_parsed::<u8>("target_pointer_width"), Some(Ok(64)));
+    /// assert!(matches!(target.get_parsed::<u8>("target_os"), Some(Err(..))));
+    /// assert!(target.get_parsed::<u8>("target_endian").is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`get`]: #method.get
+    /// [`pointer_width`]: #method.pointer_width
+    pub fn get_parsed<T>(&self, id: &str) -> Option<Result<T, T::Err>>
+    where
+        T: FromStr,
+    {
+        self.get(id).map(str::parse)
+    }
+
+    /// Returns `true` if a compiler configuration matches the corresponding identifier (ID).
+    ///
+    /// In the case of a name compiler configuration, the name is the ID. If the
+    /// compiler configuration is a key-value pair, then this will return `true`
+    /// if either the key or the value match the ID.
+    pub fn has(&self, id: &str) -> bool {
+        self.0.iter().any(|c| match c {
+            Cfg::Name(n) => n == id,
+            Cfg::KeyPair(k, v) => k == id || v == id,
+        })
+    }
+
+    /// Returns the compiler configurations that differ between this and
+    /// another compiler target.
+    ///
+    /// The first item of the returned tuple contains the configurations that
+    /// only exist for this target, and the second item contains the
+    /// configurations that only exist for the other target. Configurations are
+    /// compared as a whole, so key-value pairs with the same key but different
+    /// values, like `target_feature="sse2"` and `target_feature="avx"`, are
+    /// considered different. The order of the configurations is preserved.
+    ///
+    /// Duplicate configurations are counted, i.e. the difference is a
+    /// multiset difference, so a configuration that exists twice for this
+    /// target but only once for the other target is returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let a = "unix\ntarget_feature=\"sse\"\ntarget_feature=\"sse2\"".parse::<RustcTargetCfg>()?;
+    /// let b = "unix\ntarget_feature=\"sse\"\ntarget_feature=\"avx\"".parse::<RustcTargetCfg>()?;
+    /// let (only_a, only_b) = a.diff(&b);
+    /// assert_eq!(only_a, vec![&Cfg::KeyPair(String::from("target_feature"), String::from("sse2"))]);
+    /// assert_eq!(only_b, vec![&Cfg::KeyPair(String::from("target_feature"), String::from("avx"))]);
+    ///
+    /// let c = "unix\nunix".parse::<RustcTargetCfg>()?;
+    /// let d = "unix".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(c.diff(&d), (vec![&Cfg::Name(String::from("unix"))], Vec::new()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a RustcTargetCfg) -> (Vec<&'a Cfg>, Vec<&'a Cfg>) {
+        (only_in(&self.0, &other.0), only_in(&other.0, &self.0))
+    }
+
+    /// Returns the compiler configurations that exist for both this and
+    /// another compiler target.
+    ///
+    /// Configurations are compared as a whole, i.e. both the key and the value
+    /// must match for a key-value pair. The order of the configurations for
+    /// this target is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let a = "unix\ntarget_feature=\"sse\"\ntarget_feature=\"sse2\"".parse::<RustcTargetCfg>()?;
+    /// let b = "windows\ntarget_feature=\"sse\"\ntarget_feature=\"avx\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(
+    ///     a.intersection(&b),
+    ///     vec![&Cfg::KeyPair(String::from("target_feature"), String::from("sse"))]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intersection(&self, other: &RustcTargetCfg) -> Vec<&Cfg> {
+        self.0.iter().filter(|c| other.0.contains(c)).collect()
+    }
+
+    /// Removes duplicate compiler configurations.
+    ///
+    /// Configurations are compared as a whole, i.e. both the key and the value
+    /// must match for a key-value pair to be a duplicate, so distinct values
+    /// for the same key, like multiple `target_feature` values, are kept. The
+    /// first occurrence of each configuration is kept and the order is
+    /// preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let mut cfg = "unix\ntarget_feature=\"sse\"\nunix\ntarget_feature=\"sse2\"\ntarget_feature=\"sse\""
+    ///     .parse::<RustcTargetCfg>()?;
+    /// cfg.dedup();
+    /// assert_eq!(
+    ///     cfg,
+    ///     "unix\ntarget_feature=\"sse\"\ntarget_feature=\"sse2\"".parse::<RustcTargetCfg>()?
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut seen = HashSet::new();
+        self.0.retain(|c| seen.insert(c.clone()));
+    }
+
+    /// Returns the compiler configurations grouped by key.
+    ///
+    /// Each distinct key appears once with all of its values, even if the
+    /// values are interleaved with other compiler configurations, which is
+    /// useful for reporting, e.g. listing all `target_feature` values
+    /// together. The keys are in the order they are first seen and the values
+    /// are in their original order. A name configuration is grouped by its
+    /// name without a value, so a name without any key-value pairs has an
+    /// empty list of values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_feature=\"sse\"\nunix\ntarget_os=\"linux\"\ntarget_feature=\"sse2\"\n\
+    ///               target_has_atomic\ntarget_has_atomic=\"64\""
+    ///     .parse::<RustcTargetCfg>()?;
+    /// assert_eq!(
+    ///     target.grouped(),
+    ///     vec![
+    ///         ("target_feature", vec!["sse", "sse2"]),
+    ///         ("unix", vec![]),
+    ///         ("target_os", vec!["linux"]),
+    ///         ("target_has_atomic", vec!["64"]),
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn grouped(&self) -> Vec<(&str, Vec<&str>)> {
+        let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+        for cfg in &self.0 {
+            let (key, value) = match cfg {
+                Cfg::Name(n) => (n.as_str(), None),
+                Cfg::KeyPair(k, v) => (k.as_str(), Some(v.as_str())),
+            };
+            let index = match groups.iter().position(|(k, _)| *k == key) {
+                Some(index) => index,
+                None => {
+                    groups.push((key, Vec::new()));
+                    groups.len() - 1
+                }
+            };
+            groups[index].1.extend(value);
+        }
+        groups
+    }
+
+    /// Returns the compiler configurations with a name, or key, that is not
+    /// in a known set.
+    ///
+    /// This is useful to surface compiler configurations that are newly
+    /// introduced by the compiler, e.g. to log them, without a hardcoded list
+    /// in this crate. The order of the configurations is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "unix\ntarget_os=\"linux\"\ntarget_has_reliable_f16\nfmt_debug=\"full\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(
+    ///     target.unrecognized(&["unix", "target_os"]),
+    ///     vec![
+    ///         &Cfg::Name(String::from("target_has_reliable_f16")),
+    ///         &Cfg::KeyPair(String::from("fmt_debug"), String::from("full")),
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unrecognized(&self, known: &[&str]) -> Vec<&Cfg> {
+        self.0
+            .iter()
+            .filter(|c| {
+                let id = match c {
+                    Cfg::Name(n) => n,
+                    Cfg::KeyPair(k, ..) => k,
+                };
+                !known.contains(&id.as_str())
+            })
+            .collect()
+    }
+
+    /// Returns `true` if a presence-style compiler configuration is enabled.
+    ///
+    /// This is a forward-compatible way to query capability configurations,
+    /// like `target_thread_local` or `target_has_reliable_f16`, without a
+    /// dedicated method for each one. This returns `true` if a name
+    /// configuration matches the name or a key-value pair configuration with a
+    /// key matching the name has a truthy value, which is one of `true`,
+    /// `yes`, `on`, or `1`. Otherwise, this returns `false`.
+    ///
+    /// Unlike the [`has`] method, the value of a key-value pair is never
+    /// compared with the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_thread_local\nfeature_x=\"true\"\nfeature_y=\"false\"\nunix"
+    ///     .parse::<RustcTargetCfg>()?;
+    /// assert!(target.capability("target_thread_local"));
+    /// assert!(target.capability("feature_x"));
+    /// assert!(!target.capability("feature_y"));
+    /// assert!(!target.capability("target_has_reliable_f16"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`has`]: #method.has
+    pub fn capability(&self, name: &str) -> bool {
+        self.0.iter().any(|c| match c {
+            Cfg::Name(n) => n == name,
+            Cfg::KeyPair(k, v) => k == name && matches!(v.as_str(), "true" | "yes" | "on" | "1"),
+        })
+    }
+
+    /// Returns `true` if the `debug_assertions` compiler configuration exists.
+    ///
+    /// The `debug_assertions` configuration is a name configuration, where its
+    /// presence indicates debug assertions are enabled. Debug assertions can be
+    /// disabled with the `-C debug-assertions=off` rustc argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let dev = cargo_rustc_cfg::host()?;
+    /// assert!(dev.debug_assertions());
+    ///
+    /// let release = CargoRustcPrintCfg::default()
+    ///     .rustc_args(&["-C", "debug-assertions=off"])
+    ///     .execute()?
+    ///     .pop()
+    ///     .expect("Compiler configuration");
+    /// assert!(!release.debug_assertions());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn debug_assertions(&self) -> bool {
+        self.0.iter().any(|c| c.name() == Some("debug_assertions"))
+    }
+
+    /// Returns `true` if the `overflow_checks` compiler configuration exists.
+    ///
+    /// Similar to `debug_assertions`, the `overflow_checks` configuration is a
+    /// name configuration, where its presence indicates integer overflow checks
+    /// are enabled. Older toolchains do not emit this configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "debug_assertions\noverflow_checks\nunix".parse::<RustcTargetCfg>()?;
+    /// assert!(target.overflow_checks());
+    ///
+    /// let target = "unix".parse::<RustcTargetCfg>()?;
+    /// assert!(!target.overflow_checks());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn overflow_checks(&self) -> bool {
+        self.0.iter().any(|c| c.name() == Some("overflow_checks"))
+    }
+
+    /// Returns `true` if the target appears to be a bare-metal target without
+    /// an operating system.
+    ///
+    /// This is a heuristic for targets that are likely `no_std`-only. It only
+    /// checks the `target_os` compiler configuration and returns `true` if it
+    /// does not exist, has an empty value, i.e. `target_os=""`, or has the
+    /// `none` value, i.e. `target_os="none"`, as emitted for targets like
+    /// `thumbv7em-none-eabihf`. Note, targets with an unknown operating system,
+    /// like `wasm32-unknown-unknown` with `target_os="unknown"`, are _not_
+    /// considered bare-metal, even though they may not provide the standard
+    /// library either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let thumbv7em = cargo_rustc_cfg::target("thumbv7em-none-eabihf")?;
+    /// assert!(thumbv7em.is_bare_metal());
+    ///
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert!(!linux.is_bare_metal());
+    ///
+    /// let wasm = cargo_rustc_cfg::target("wasm32-unknown-unknown")?;
+    /// assert!(!wasm.is_bare_metal());
+    ///
+    /// let empty = "target_os=\"\"".parse::<RustcTargetCfg>()?;
+    /// assert!(empty.is_bare_metal());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_bare_metal(&self) -> bool {
+        matches!(self.target_os(), None | Some("") | Some("none"))
+    }
+
+    /// Returns `true` if artifacts compiled for this target are likely
+    /// compatible with artifacts compiled for another target.
+    ///
+    /// This is a heuristic, not a guarantee. Only the `target_arch`,
+    /// `target_os`, `target_env`, and `target_pointer_width` compiler
+    /// configurations are compared, and a configuration that is missing from
+    /// both targets is considered equal. Everything else is ignored, including
+    /// incidental differences like the `debug_assertions` configuration, but
+    /// also the `target_vendor`, `target_abi`, `target_endian`, and
+    /// `target_feature` configurations, e.g. the `mips` and `mipsel` targets
+    /// have the same `target_arch` but a different endianness, and a target
+    /// feature can change the calling convention of floating point values.
+    /// The compiler does not promise a stable ABI between versions either, so
+    /// the toolchain has to be compared separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let release = "target_arch=\"x86_64\"\ntarget_os=\"linux\"\ntarget_env=\"gnu\"\ntarget_pointer_width=\"64\"".parse::<RustcTargetCfg>()?;
+    /// let debug = "debug_assertions\ntarget_arch=\"x86_64\"\ntarget_os=\"linux\"\ntarget_env=\"gnu\"\ntarget_pointer_width=\"64\"".parse::<RustcTargetCfg>()?;
+    /// assert!(debug.abi_compatible_with(&release));
+    ///
+    /// let gnu = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// let gnux32 = cargo_rustc_cfg::target("x86_64-unknown-linux-gnux32")?;
+    /// assert_eq!(gnu.target_arch(), gnux32.target_arch());
+    /// assert_eq!(gnu.target_env(), gnux32.target_env());
+    /// assert!(gnu.abi_compatible_with(&gnu));
+    /// assert!(!gnux32.abi_compatible_with(&gnu));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn abi_compatible_with(&self, other: &RustcTargetCfg) -> bool {
+        [
+            "target_arch",
+            "target_os",
+            "target_env",
+            "target_pointer_width",
+        ]
+        .iter()
+        .all(|id| self.get(id) == other.get(id))
+    }
+
+    /// Returns `true` if compiling for this target is cross-compiling
+    /// relative to the host.
+    ///
+    /// Only the salient compiler configurations are compared, i.e. the
+    /// `target_arch`, `target_os`, and `target_env` configurations, so
+    /// differences from compiler flags, like the `target_feature` or
+    /// `debug_assertions` configurations, are ignored. The host compiler
+    /// configurations are available from the [`host`] function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let host = cargo_rustc_cfg::host()?;
+    /// assert!(!host.is_cross_for(&host));
+    ///
+    /// let thumbv7em = cargo_rustc_cfg::target("thumbv7em-none-eabihf")?;
+    /// assert!(thumbv7em.is_cross_for(&host));
+    ///
+    /// let gnu = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// let musl = cargo_rustc_cfg::target("x86_64-unknown-linux-musl")?;
+    /// assert!(musl.is_cross_for(&gnu));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`host`]: fn.host.html
+    pub fn is_cross_for(&self, host: &RustcTargetCfg) -> bool {
+        self.target_arch() != host.target_arch()
+            || self.target_os() != host.target_os()
+            || self.target_env() != host.target_env()
+    }
+
+    /// Returns `true` if the target has 32-bit pointers.
+    ///
+    /// This returns `false` if the pointer width is unknown.
+    ///
+    /// See the [`pointer_width`] method for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let x86 = cargo_rustc_cfg::target("i686-pc-windows-msvc")?;
+    /// assert!(x86.is_32_bit());
+    /// assert!(!x86.is_64_bit());
+    ///
+    /// let msp430 = cargo_rustc_cfg::target("msp430-none-elf")?;
+    /// assert!(!msp430.is_32_bit());
+    /// assert!(!msp430.is_64_bit());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`pointer_width`]: #method.pointer_width
+    pub fn is_32_bit(&self) -> bool {
+        self.pointer_width() == Some(32)
+    }
+
+    /// Returns `true` if the target has 64-bit pointers.
+    ///
+    /// This returns `false` if the pointer width is unknown.
+    ///
+    /// See the [`pointer_width`] method for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let x86_64 = cargo_rustc_cfg::target("x86_64-pc-windows-msvc")?;
+    /// assert!(x86_64.is_64_bit());
+    /// assert!(!x86_64.is_32_bit());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`pointer_width`]: #method.pointer_width
+    pub fn is_64_bit(&self) -> bool {
+        self.pointer_width() == Some(64)
+    }
+
+    /// Returns `true` if the target belongs to the Unix family.
+    ///
+    /// This checks both the `unix` name configuration and the
+    /// `target_family="unix"` compiler configuration. A target can belong to
+    /// more than one family, e.g. `wasm32-unknown-emscripten` belongs to both
+    /// the `unix` and `wasm` families, so this is `true` if any of its
+    /// families is `unix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert!(linux.is_unix());
+    ///
+    /// let emscripten = cargo_rustc_cfg::target("wasm32-unknown-emscripten")?;
+    /// assert!(emscripten.is_unix());
+    /// assert!(emscripten.is_wasm());
+    ///
+    /// let windows = cargo_rustc_cfg::target("x86_64-pc-windows-msvc")?;
+    /// assert!(!windows.is_unix());
+    ///
+    /// let wasm = cargo_rustc_cfg::target("wasm32-unknown-unknown")?;
+    /// assert!(!wasm.is_unix());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_unix(&self) -> bool {
+        self.is_family("unix")
+    }
+
+    /// Returns `true` if the target is a WebAssembly (WASM) target.
+    ///
+    /// This checks both the `target_arch` compiler configuration for the
+    /// `wasm32` and `wasm64` values and the `target_family="wasm"` compiler
+    /// configuration emitted by newer compilers, so targets with either one
+    /// are considered WASM targets, regardless of the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let unknown = cargo_rustc_cfg::target("wasm32-unknown-unknown")?;
+    /// assert!(unknown.is_wasm());
+    ///
+    /// // The successor of the `wasm32-wasi` target
+    /// let wasi = cargo_rustc_cfg::target("wasm32-wasip1")?;
+    /// assert!(wasi.is_wasm());
+    ///
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert!(!linux.is_wasm());
+    ///
+    /// let family = "target_family=\"wasm\"".parse::<RustcTargetCfg>()?;
+    /// assert!(family.is_wasm());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_wasm(&self) -> bool {
+        matches!(self.target_arch(), Some("wasm32") | Some("wasm64"))
+            || self.key_pairs().any(|p| p == ("target_family", "wasm"))
+    }
+
+    /// Returns `true` if the target belongs to the Windows family.
+    ///
+    /// This checks both the `windows` name configuration and the
+    /// `target_family="windows"` compiler configuration, including targets
+    /// that belong to more than one family.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let windows = cargo_rustc_cfg::target("x86_64-pc-windows-msvc")?;
+    /// assert!(windows.is_windows());
+    ///
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert!(!linux.is_windows());
+    ///
+    /// let wasm = cargo_rustc_cfg::target("wasm32-unknown-unknown")?;
+    /// assert!(!wasm.is_windows());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_windows(&self) -> bool {
+        self.is_family("windows")
+    }
+
+    /// Returns `true` if the target belongs to a family, either by the name
+    /// configuration or a `target_family` configuration.
+    fn is_family(&self, family: &str) -> bool {
+        self.0.iter().any(|c| match c {
+            Cfg::Name(n) => n == family,
+            Cfg::KeyPair(k, v) => k == "target_family" && v == family,
+        })
+    }
+
+    /// Returns the value of the `target_pointer_width` compiler configuration
+    /// as a number of bits.
+    ///
+    /// This will return `None` if the `target_pointer_width` configuration does
+    /// not exist or its value is not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let msp430 = cargo_rustc_cfg::target("msp430-none-elf")?;
+    /// assert_eq!(msp430.pointer_width(), Some(16));
+    ///
+    /// let unknown = "unix".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(unknown.pointer_width(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pointer_width(&self) -> Option<u32> {
+        self.get_parsed("target_pointer_width").and_then(Result::ok)
+    }
+
+    /// Returns the target triple components derived from the compiler
+    /// configurations.
+    ///
+    /// A compiler configuration does not include the target triple, so the
+    /// components are the values of the `target_arch`, `target_vendor`,
+    /// `target_os`, and `target_env` compiler configurations. These may
+    /// differ from the components of the actual target triple, e.g. the
+    /// architecture of the `i686-pc-windows-msvc` target is `x86`. An empty
+    /// vendor or environment is `None`. This returns `None` if the
+    /// `target_arch` or `target_os` configuration does not exist.
+    ///
+    /// The target triple of a labeled compiler configuration from the
+    /// [`Targets`] type can be parsed with the `FromStr` implementation of the
+    /// [`TargetTriple`] type instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = cargo_rustc_cfg::target("i686-pc-windows-msvc")?;
+    /// let triple = target.parsed_triple().expect("Target triple");
+    /// assert_eq!(triple.to_string(), "x86-pc-windows-msvc");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Targets`]: struct.Targets.html
+    /// [`TargetTriple`]: struct.TargetTriple.html
+    pub fn parsed_triple(&self) -> Option<TargetTriple> {
+        let non_empty = |v: Option<&str>| v.filter(|v| !v.is_empty()).map(String::from);
+        Some(TargetTriple {
+            arch: non_empty(self.target_arch())?,
+            vendor: non_empty(self.target_vendor()),
+            os: non_empty(self.target_os())?,
+            env: non_empty(self.target_env()),
+        })
+    }
+
+    /// Returns the value of the `target_arch` compiler configuration as an
+    /// [`Arch`].
+    ///
+    /// This will return `None` if the `target_arch` configuration does not
+    /// exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Arch, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let x86 = cargo_rustc_cfg::target("i686-pc-windows-msvc")?;
+    /// assert_eq!(x86.arch(), Some(Arch::X86));
+    ///
+    /// let x86_64 = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert_eq!(x86_64.arch(), Some(Arch::X86_64));
+    ///
+    /// let aarch64 = cargo_rustc_cfg::target("aarch64-apple-darwin")?;
+    /// assert_eq!(aarch64.arch(), Some(Arch::Aarch64));
+    ///
+    /// let wasm = cargo_rustc_cfg::target("wasm32-unknown-unknown")?;
+    /// assert_eq!(wasm.arch(), Some(Arch::Wasm32));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Arch`]: enum.Arch.html
+    pub fn arch(&self) -> Option<Arch> {
+        self.target_arch().map(Arch::from)
+    }
+
+    /// Returns the value of the `target_os` compiler configuration as an
+    /// [`Os`].
+    ///
+    /// This will return `None` if the `target_os` configuration does not
+    /// exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, Os};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert_eq!(linux.os(), Some(Os::Linux));
+    ///
+    /// let windows = cargo_rustc_cfg::target("x86_64-pc-windows-msvc")?;
+    /// assert_eq!(windows.os(), Some(Os::Windows));
+    ///
+    /// let thumbv7em = cargo_rustc_cfg::target("thumbv7em-none-eabihf")?;
+    /// assert_eq!(thumbv7em.os(), Some(Os::None));
+    ///
+    /// let wasi = cargo_rustc_cfg::target("wasm32-wasip1")?;
+    /// assert_eq!(wasi.os(), Some(Os::Wasi));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Os`]: enum.Os.html
+    pub fn os(&self) -> Option<Os> {
+        self.target_os().map(Os::from)
+    }
+
+    /// Returns the commonly used compiler configurations at once.
+    ///
+    /// This composes the [`arch`], [`os`], [`target_env`], and
+    /// [`pointer_width`] methods, along with the `target_family` and
+    /// `target_endian` compiler configurations, into a [`Summary`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Arch, Error, Os};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let summary = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?.summary();
+    /// assert_eq!(summary.arch, Some(Arch::X86_64));
+    /// assert_eq!(summary.os, Some(Os::Linux));
+    /// assert_eq!(summary.env.as_deref(), Some("gnu"));
+    /// assert_eq!(summary.families, vec!["unix"]);
+    /// assert_eq!(summary.pointer_width, Some(64));
+    /// assert_eq!(summary.endian.as_deref(), Some("little"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`arch`]: #method.arch
+    /// [`os`]: #method.os
+    /// [`target_env`]: #method.target_env
+    /// [`pointer_width`]: #method.pointer_width
+    /// [`Summary`]: struct.Summary.html
+    pub fn summary(&self) -> Summary {
+        Summary {
+            arch: self.arch(),
+            os: self.os(),
+            env: self.target_env().map(String::from),
+            families: self
+                .key_pairs()
+                .filter(|(k, _)| *k == "target_family")
+                .map(|(_, v)| String::from(v))
+                .collect(),
+            pointer_width: self.pointer_width(),
+            endian: self.get("target_endian").map(String::from),
+        }
+    }
+
+    /// Returns the value of the `target_abi` compiler configuration.
     ///
-    /// The `--target` argument is prepended automatically. Please do not include it
-    /// as part of the target triple value.
+    /// This will return `None` if the `target_abi` configuration does not
+    /// exist, which is the case for older toolchains, and `Some("")` if rustc
+    /// emits an empty value, i.e. `target_abi=""`.
     ///
-    /// For reference, the default command is:
+    /// # Examples
     ///
-    /// ```text
-    /// cargo rustc --print cfg
     /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let thumbv7em = cargo_rustc_cfg::target("thumbv7em-none-eabihf")?;
+    /// assert_eq!(thumbv7em.target_abi(), Some("eabihf"));
     ///
-    /// and this method would add `--target <RUSTC_TARGET>` to yield:
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert_eq!(linux.target_abi(), Some(""));
     ///
-    /// ```text
-    /// cargo rustc --target <RUSTC_TARGET> --print cfg
+    /// let older = "unix\ntarget_os=\"linux\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(older.target_abi(), None);
+    /// # Ok(())
+    /// # }
     /// ```
+    pub fn target_abi(&self) -> Option<&str> {
+        self.get("target_abi")
+    }
+
+    /// Returns the value of the `target_arch` compiler configuration.
     ///
-    /// where `<RUSTC_TARGET>` is a target triple from the `rustc --print
-    /// target-list` output.
-    ///
-    /// If more than one rustc target is specified, the `-Z multitarget` option
-    /// will automatically be added to the command invocation.
+    /// This will return `None` if the `target_arch` configuration does not
+    /// exist.
     ///
     /// # Examples
     ///
-    /// The compiler configuration for the `i686-unknown-linux-gnu` target regardless of the host.
-    ///
     /// ```
     /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use cargo_rustc_cfg::Error;
     /// # fn main() -> std::result::Result<(), Error> {
-    /// let target = CargoRustcPrintCfg::default()
-    ///     .rustc_target("i686-unknown-linux-gnu")
-    ///     .execute()?
-    ///     .pop()
-    ///     .expect("Compiler configuration");
-    /// assert_eq!(target.get("target_arch"), Some("x86"));
-    /// assert_eq!(target.get("target_endian"), Some("little"));
-    /// assert_eq!(target.get("target_env"), Some("gnu"));
-    /// assert_eq!(target.get("target_family"), Some("unix"));
-    /// assert_eq!(target.get("target_os"), Some("linux"));
-    /// assert_eq!(target.get("target_pointer_width"), Some("32"));
-    /// assert_eq!(target.get("target_vendor"), Some("unknown"));
-    /// assert_eq!(target.get("unix"), Some("unix"));
+    /// let target = cargo_rustc_cfg::target("i686-unknown-linux-gnu")?;
+    /// assert_eq!(target.target_arch(), Some("x86"));
     /// # Ok(())
     /// # }
     /// ```
+    pub fn target_arch(&self) -> Option<&str> {
+        self.get("target_arch")
+    }
+
+    /// Returns the value of the `target_env` compiler configuration.
     ///
-    /// This method can also be used to specify multiple targets. Note, the
-    /// `multitarget` feature must be enabled. The following example uses
-    /// [`cargo_args`] to enable the multitarget feature (nightly-only).
+    /// This will return `None` if the `target_env` configuration does not
+    /// exist. Some targets do not have an environment and rustc emits an empty
+    /// value, i.e. `target_env=""`, for them. In this case, `Some("")` is
+    /// returned. Use the [`target_env_or_empty`] method to treat both cases the
+    /// same.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use cargo_rustc_cfg::Error;
     /// # fn main() -> std::result::Result<(), Error> {
-    /// let targets = CargoRustcPrintCfg::default()
-    ///     .cargo_args(&["-Z", "multitarget"])
-    ///     .rustc_target("i686-pc-windows-msvc")
-    ///     .rustc_target("i686-pc-windows-gnu")
-    ///     .execute()?;
-    /// let gnu = targets.get(0).expect("i686-pc-windows-gnu target");
-    /// let msvc = targets.get(1).expect("i686-pc-windows-msvc target");
-    ///
-    /// assert_eq!(msvc.get("target_arch"), Some("x86"));
-    /// assert_eq!(msvc.get("target_endian"), Some("little"));
-    /// assert_eq!(msvc.get("target_env"), Some("msvc"));
-    /// assert_eq!(msvc.get("target_family"), Some("windows"));
-    /// assert_eq!(msvc.get("target_os"), Some("windows"));
-    /// assert_eq!(msvc.get("target_pointer_width"), Some("32"));
-    /// assert_eq!(msvc.get("target_vendor"), Some("pc"));
-    /// assert_eq!(msvc.get("windows"), Some("windows"));
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert_eq!(linux.target_env(), Some("gnu"));
     ///
-    /// assert_eq!(gnu.get("target_arch"), Some("x86"));
-    /// assert_eq!(gnu.get("target_endian"), Some("little"));
-    /// assert_eq!(gnu.get("target_env"), Some("gnu"));
-    /// assert_eq!(gnu.get("target_family"), Some("windows"));
-    /// assert_eq!(gnu.get("target_os"), Some("windows"));
-    /// assert_eq!(gnu.get("target_pointer_width"), Some("32"));
-    /// assert_eq!(gnu.get("target_vendor"), Some("pc"));
-    /// assert_eq!(gnu.get("windows"), Some("windows"));
+    /// let macos = cargo_rustc_cfg::target("x86_64-apple-darwin")?;
+    /// assert_eq!(macos.target_env(), Some(""));
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`cargo_args`]: #method.cargo_args
-    pub fn rustc_target<T>(&mut self, t: T) -> &mut Self
-    where
-        T: AsRef<OsStr>,
-    {
-        self.rustc_targets.push(t.as_ref().into());
-        self
+    /// [`target_env_or_empty`]: #method.target_env_or_empty
+    pub fn target_env(&self) -> Option<&str> {
+        self.get("target_env")
     }
 
-    /// Specify multiple Rust compiler (rustc) targets via target triples.
+    /// Returns the value of the `target_env` compiler configuration or an
+    /// empty string.
     ///
-    /// The `--target` argument is prepended automatically. Please do not include it
-    /// as part of the target triple value.
+    /// The [`target_env`] method distinguishes between an empty value, i.e.
+    /// `target_env=""` emitted for targets like `x86_64-apple-darwin`, and a
+    /// missing `target_env` configuration. This method treats both cases the
+    /// same and returns an empty string, which makes checking for a meaningful
+    /// environment unambiguous.
     ///
-    /// For reference, the default command is:
+    /// # Examples
     ///
-    /// ```text
-    /// cargo rustc --print cfg
     /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let macos = cargo_rustc_cfg::target("x86_64-apple-darwin")?;
+    /// assert_eq!(macos.target_env(), Some(""));
+    /// assert_eq!(macos.target_env_or_empty(), "");
     ///
-    /// and this method would add multiple `--target <RUSTC_TARGET>` to yield:
+    /// let missing = "unix\ntarget_os=\"linux\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(missing.target_env(), None);
+    /// assert_eq!(missing.target_env_or_empty(), "");
     ///
-    /// ```text
-    /// cargo rustc --target <RUSTC_TARGET_1> --target <RUSTC_TARGET_2> --print cfg
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert_eq!(linux.target_env_or_empty(), "gnu");
+    /// # Ok(())
+    /// # }
     /// ```
     ///
-    /// where `<RUSTC_TARGET>` is a target triple from the `rustc --print
-    /// target-list` output.
+    /// [`target_env`]: #method.target_env
+    pub fn target_env_or_empty(&self) -> &str {
+        self.target_env().unwrap_or("")
+    }
+
+    /// Returns the value of the `target_os` compiler configuration.
     ///
-    /// If multiple rustc targets are specified, then the `-Z multitarget`
-    /// option must be added using the [`cargo_args`] method or specified in the
-    /// project's `.cargo/config.toml` file as follows:
+    /// This will return `None` if the `target_os` configuration does not
+    /// exist.
+    ///
+    /// # Examples
     ///
-    /// ```toml
-    /// [unstable]
-    /// multitarget = true
     /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = cargo_rustc_cfg::target("x86_64-pc-windows-msvc")?;
+    /// assert_eq!(target.target_os(), Some("windows"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn target_os(&self) -> Option<&str> {
+        self.get("target_os")
+    }
+
+    /// Returns `true` if the target supports thread-local storage.
     ///
-    /// # Examples
+    /// This checks the `target_thread_local` compiler configuration with the
+    /// [`capability`] method.
     ///
-    /// Specifying multiple targets and enabling the `-Z multitarget` feature
-    /// with the [`cargo_args`] method (nightly-only):
+    /// # Examples
     ///
     /// ```
     /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use cargo_rustc_cfg::Error;
     /// # fn main() -> std::result::Result<(), Error> {
-    /// let targets = CargoRustcPrintCfg::default()
-    ///     .cargo_args(&["-Z", "multitarget"])
-    ///     .rustc_targets(&["i686-pc-windows-msvc", "i686-pc-windows-gnu"])
-    ///     .execute()?;
-    /// let gnu = targets.get(0).expect("i686-pc-windows-gnu target");
-    /// let msvc = targets.get(1).expect("i686-pc-windows-msvc target");
-    ///
-    /// assert_eq!(msvc.get("target_arch"), Some("x86"));
-    /// assert_eq!(msvc.get("target_endian"), Some("little"));
-    /// assert_eq!(msvc.get("target_env"), Some("msvc"));
-    /// assert_eq!(msvc.get("target_family"), Some("windows"));
-    /// assert_eq!(msvc.get("target_os"), Some("windows"));
-    /// assert_eq!(msvc.get("target_pointer_width"), Some("32"));
-    /// assert_eq!(msvc.get("target_vendor"), Some("pc"));
-    /// assert_eq!(msvc.get("windows"), Some("windows"));
+    /// let linux = cargo_rustc_cfg::target("x86_64-unknown-linux-gnu")?;
+    /// assert!(linux.target_thread_local());
     ///
-    /// assert_eq!(gnu.get("target_arch"), Some("x86"));
-    /// assert_eq!(gnu.get("target_endian"), Some("little"));
-    /// assert_eq!(gnu.get("target_env"), Some("gnu"));
-    /// assert_eq!(gnu.get("target_family"), Some("windows"));
-    /// assert_eq!(gnu.get("target_os"), Some("windows"));
-    /// assert_eq!(gnu.get("target_pointer_width"), Some("32"));
-    /// assert_eq!(gnu.get("target_vendor"), Some("pc"));
-    /// assert_eq!(gnu.get("windows"), Some("windows"));
+    /// let thumbv7em = cargo_rustc_cfg::target("thumbv7em-none-eabihf")?;
+    /// assert!(!thumbv7em.target_thread_local());
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`cargo_args`]: #method.cargo_args
-    pub fn rustc_targets<T>(&mut self, t: &[T]) -> &mut Self
-    where
-        T: AsRef<OsStr>,
-    {
-        self.rustc_targets.append(
-            &mut t
-                .iter()
-                .map(|t| t.as_ref().into())
-                .collect::<Vec<OsString>>(),
-        );
-        self
+    /// [`capability`]: #method.capability
+    pub fn target_thread_local(&self) -> bool {
+        self.capability("target_thread_local")
     }
 
-    /// This executes the `cargo rustc` subcommand with the appropriate options.
+    /// Returns the value of the `target_vendor` compiler configuration.
     ///
-    /// For reference, the generic command signature:
+    /// This will return `None` if the `target_vendor` configuration does not
+    /// exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::Error;
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = cargo_rustc_cfg::target("x86_64-apple-darwin")?;
+    /// assert_eq!(target.target_vendor(), Some("apple"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn target_vendor(&self) -> Option<&str> {
+        self.get("target_vendor")
+    }
+
+    /// Evaluates a conditional compilation predicate against the compiler
+    /// configurations.
+    ///
+    /// The predicate is the content of a `#[cfg(...)]` attribute, like
+    /// `all(target_os = "linux", target_pointer_width = "64")`. The supported
+    /// grammar is:
     ///
     /// ```text
-    /// `cargo +<TOOLCHAIN> rustc <CARGO_ARGS> <RUSTC_TARGETS> --print cfg -- <RUSTC_ARGS>`
+    /// predicate = name | name "=" string
+    ///           | "all" "(" [list] ")" | "any" "(" [list] ")"
+    ///           | "not" "(" predicate ")"
+    /// list      = predicate { "," predicate } [","]
     /// ```
     ///
-    /// where `<TOOLCHAIN>` is replaced with the [`cargo_toolchain`] value, the
-    /// `<CARGO_ARGS>` is replaced with the [`cargo_args`] value, the
-    /// `<RUSTC_TARGETS>` is appropriately replaced with `--target
-    /// <RUSTC_TARGET> for each specified target from the [`rustc_targets`] or
-    /// [`rustc_target`] methods, and the `<RUSTC_ARGS>` is replaced with the
-    /// [`rustc_args`] value.
+    /// where a name is an identifier and a string is enclosed in double quotes,
+    /// optionally with `\"` and `\\` escapes. A name is `true` if a name
+    /// configuration matches, and a key-value pair is `true` if a key-value
+    /// pair configuration matches both the key and the value. The `true` and
+    /// `false` names are literals. As with the compiler, `all()` is `true` and
+    /// `any()` is `false`.
     ///
-    /// # Examples
+    /// An error is returned for any other construct, e.g. a missing
+    /// parenthesis, an unquoted value, or trailing content.
     ///
-    /// For a Windows target:
+    /// # Examples
     ///
     /// ```
     /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
     /// # fn main() -> std::result::Result<(), Error> {
-    /// let windows = CargoRustcPrintCfg::default()
-    ///     .rustc_target("x86_64-pc-windows-msvc")
-    ///     .execute()?
-    ///     .pop()
-    ///     .expect("Compiler configuration");
-    /// assert_eq!(windows.get("target_arch"), Some("x86_64"));
-    /// assert_eq!(windows.get("target_endian"), Some("little"));
-    /// assert_eq!(windows.get("target_env"), Some("msvc"));
-    /// assert_eq!(windows.get("target_family"), Some("windows"));
-    /// assert_eq!(windows.get("target_os"), Some("windows"));
-    /// assert_eq!(windows.get("target_pointer_width"), Some("64"));
-    /// assert_eq!(windows.get("target_vendor"), Some("pc"));
-    /// assert_eq!(windows.get("windows"), Some("windows"));
+    /// let target = "unix\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\"".parse::<RustcTargetCfg>()?;
+    /// assert!(target.evaluate(r#"all(target_os = "linux", target_pointer_width = "64")"#)?);
+    /// assert!(target.evaluate(r#"any(windows, not(target_os = "macos"))"#)?);
+    /// assert!(!target.evaluate("windows")?);
+    /// assert!(target.evaluate("all(unix,").is_err());
+    /// assert!(target.evaluate("target_os = linux").is_err());
     /// # Ok(())
     /// # }
     /// ```
+    pub fn evaluate(&self, predicate: &str) -> Result<bool, Error> {
+        let mut parser = PredicateParser {
+            cfgs: self,
+            input: predicate,
+            position: 0,
+        };
+        let result = parser.predicate()?;
+        parser.skip_whitespace();
+        if parser.position != predicate.len() {
+            return Err(parser.error("unexpected trailing content"));
+        }
+        Ok(result)
+    }
+
+    /// Renders the compiler configurations as a conditional compilation
+    /// predicate.
     ///
-    /// For a Linux target:
+    /// The predicate is a compact, single-line `all(...)` expression of every
+    /// configuration, which can be used directly within a `#[cfg(...)]`
+    /// attribute. Name configurations are rendered as-is and key-value pair
+    /// configurations are rendered as `key = "value"`. This is useful for code
+    /// generators that emit conditional compilation attributes based on a
+    /// probed target. An empty container renders as `all()`, which is always
+    /// true.
+    ///
+    /// The multi-line form that mirrors the output of the `cargo rustc --print
+    /// cfg` command is available with the `Display` implementation.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
     /// # fn main() -> std::result::Result<(), Error> {
-    /// let linux = CargoRustcPrintCfg::default()
-    ///     .rustc_target("x86_64-unknown-linux-gnu")
-    ///     .execute()?
-    ///     .pop()
-    ///     .expect("Compiler configuration");
-    /// assert_eq!(linux.get("target_arch"), Some("x86_64"));
-    /// assert_eq!(linux.get("target_endian"), Some("little"));
-    /// assert_eq!(linux.get("target_env"), Some("gnu"));
-    /// assert_eq!(linux.get("target_family"), Some("unix"));
-    /// assert_eq!(linux.get("target_os"), Some("linux"));
-    /// assert_eq!(linux.get("target_pointer_width"), Some("64"));
-    /// assert_eq!(linux.get("target_vendor"), Some("unknown"));
-    /// assert_eq!(linux.get("unix"), Some("unix"));
+    /// let target = "unix\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\"".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(
+    ///     target.to_cfg_expression(),
+    ///     r#"all(unix, target_os = "linux", target_pointer_width = "64")"#
+    /// );
+    /// assert_eq!(
+    ///     target.to_string(),
+    ///     "unix\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\""
+    /// );
     /// # Ok(())
     /// # }
     /// ```
+    pub fn to_cfg_expression(&self) -> String {
+        let predicates = self
+            .0
+            .iter()
+            .map(|c| match c {
+                Cfg::Name(n) => n.clone(),
+                Cfg::KeyPair(k, v) => format!("{} = {:?}", k, v),
+            })
+            .collect::<Vec<String>>();
+        format!("all({})", predicates.join(", "))
+    }
+
+    /// Renders the compiler configurations exactly as the output of the `cargo
+    /// rustc --print cfg` command.
     ///
-    /// If the host is an macOS target:
+    /// Each configuration is written on its own line, including the last one,
+    /// with name configurations as-is and key-value pair configurations as
+    /// `key="value"`. This differs from the `Display` implementation, which
+    /// does not terminate the last line, and it guarantees output that can be
+    /// re-ingested by tooling expecting the compiler's format, such as the
+    /// [`parse_output`] and [`from_file`] functions, e.g. for writing a
+    /// snapshot file. An empty container renders as an empty string.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # extern crate cargo_rustc_cfg;
-    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
     /// # fn main() -> std::result::Result<(), Error> {
-    /// let macos = CargoRustcPrintCfg::default()
-    ///     .rustc_target("x86_64-apple-darwin")
-    ///     .execute()?
-    ///     .pop()
-    ///     .expect("Compiler configuration");
-    /// assert_eq!(macos.get("target_arch"), Some("x86_64"));
-    /// assert_eq!(macos.get("target_endian"), Some("little"));
-    /// assert_eq!(macos.get("target_env"), Some(""));
-    /// assert_eq!(macos.get("target_family"), Some("unix"));
-    /// assert_eq!(macos.get("target_os"), Some("macos"));
-    /// assert_eq!(macos.get("target_pointer_width"), Some("64"));
-    /// assert_eq!(macos.get("target_vendor"), Some("apple"));
-    /// assert_eq!(macos.get("unix"), Some("unix"));
+    /// // Recorded from `rustc --print cfg --target x86_64-unknown-linux-gnu`
+    /// let recorded = "debug_assertions
+    /// panic=\"unwind\"
+    /// target_abi=\"\"
+    /// target_arch=\"x86_64\"
+    /// target_endian=\"little\"
+    /// target_env=\"gnu\"
+    /// target_family=\"unix\"
+    /// target_feature=\"fxsr\"
+    /// target_feature=\"sse\"
+    /// target_feature=\"sse2\"
+    /// target_has_atomic=\"16\"
+    /// target_has_atomic=\"32\"
+    /// target_has_atomic=\"64\"
+    /// target_has_atomic=\"8\"
+    /// target_has_atomic=\"ptr\"
+    /// target_os=\"linux\"
+    /// target_pointer_width=\"64\"
+    /// target_vendor=\"unknown\"
+    /// unix
+    /// ";
+    /// let target = recorded.parse::<RustcTargetCfg>()?;
+    /// assert_eq!(target.to_rustc_format(), recorded);
+    /// assert_eq!(target.to_rustc_format().parse::<RustcTargetCfg>()?, target);
+    /// assert_eq!(RustcTargetCfg::from(Vec::new()).to_rustc_format(), "");
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`cargo_toolchain`]: #method.cargo_toolchain
-    /// [`cargo_args`]: #method.cargo_args
-    /// [`rustc_targets`]: #method.rustc_targets
-    /// [`rustc_target`]: #method.rustc_target
-    /// [`rustc_args`]: #method.rustc_args
-    pub fn execute(&self) -> Result<Vec<RustcTargetCfg>, Error> {
-        let mut cmd = Command::new(
-            env::var(CARGO_VARIABLE)
-                .map(PathBuf::from)
-                .ok()
-                .unwrap_or_else(|| PathBuf::from(CARGO)),
-        );
-        if let Some(toolchain) = &self.cargo_toolchain {
-            let mut arg = OsString::from("+");
-            arg.push(toolchain);
-            cmd.arg(arg);
+    /// [`parse_output`]: fn.parse_output.html
+    /// [`from_file`]: fn.from_file.html
+    pub fn to_rustc_format(&self) -> String {
+        self.0.iter().map(|c| format!("{}\n", c)).collect()
+    }
+
+    /// Writes the compiler configurations as a TOML table.
+    ///
+    /// This is only available with the `toml` feature. The configurations are
+    /// mapped to one key-value pair per line, in the order of the first
+    /// occurrence of each key, with the following rules:
+    ///
+    /// - A name, such as `unix`, becomes `unix = true`.
+    /// - A key that occurs once, such as `target_os="linux"`, becomes a
+    ///   string, `target_os = "linux"`.
+    /// - A key that occurs more than once, such as `target_feature`, becomes
+    ///   an array of strings in the order of the output, `target_feature =
+    ///   ["fxsr", "sse", "sse2"]`.
+    ///
+    /// A name that is also used as a key is represented by the values of the
+    /// key. Keys that are not valid TOML bare keys are quoted, and values are
+    /// escaped as TOML basic strings. This is the same grouping as the
+    /// [`grouped`] method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// # #[cfg(feature = "toml")]
+    /// # {
+    /// let target = "debug_assertions
+    /// target_abi=\"\"
+    /// target_arch=\"x86_64\"
+    /// target_feature=\"fxsr\"
+    /// target_feature=\"sse\"
+    /// target_feature=\"sse2\"
+    /// target_os=\"linux\"
+    /// unix".parse::<RustcTargetCfg>()?;
+    /// assert_eq!(
+    ///     target.to_toml(),
+    ///     "debug_assertions = true
+    /// target_abi = \"\"
+    /// target_arch = \"x86_64\"
+    /// target_feature = [\"fxsr\", \"sse\", \"sse2\"]
+    /// target_os = \"linux\"
+    /// unix = true
+    /// "
+    /// );
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`grouped`]: #method.grouped
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> String {
+        self.grouped()
+            .into_iter()
+            .map(|(key, values)| {
+                let value = match values.as_slice() {
+                    [] => String::from("true"),
+                    [value] => toml_string(value),
+                    values => format!(
+                        "[{}]",
+                        values
+                            .iter()
+                            .map(|v| toml_string(v))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                };
+                if !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    format!("{} = {}\n", key, value)
+                } else {
+                    format!("{} = {}\n", toml_string(key), value)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Quotes and escapes a string as a TOML basic string.
+#[cfg(feature = "toml")]
+fn toml_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
         }
-        cmd.arg(RUSTC);
-        if let Some(manifest_path) = &self.manifest_path {
-            cmd.arg("--manifest-path");
-            cmd.arg(manifest_path);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A parser that evaluates a conditional compilation predicate against
+/// compiler configurations.
+///
+/// This is used by the [`RustcTargetCfg::evaluate`] method.
+///
+/// [`RustcTargetCfg::evaluate`]: struct.RustcTargetCfg.html#method.evaluate
+struct PredicateParser<'a> {
+    cfgs: &'a RustcTargetCfg,
+    input: &'a str,
+    position: usize,
+}
+
+impl PredicateParser<'_> {
+    fn predicate(&mut self) -> Result<bool, Error> {
+        let name = self.identifier()?;
+        self.skip_whitespace();
+        match name.as_str() {
+            "all" | "any" if self.peek() == Some('(') => {
+                let values = self.list()?;
+                Ok(if name == "all" {
+                    values.into_iter().all(|v| v)
+                } else {
+                    values.into_iter().any(|v| v)
+                })
+            }
+            "not" if self.peek() == Some('(') => {
+                self.expect('(')?;
+                let value = self.predicate()?;
+                self.expect(')')?;
+                Ok(!value)
+            }
+            _ if self.peek() == Some('=') => {
+                self.expect('=')?;
+                let value = self.string()?;
+                Ok(self
+                    .cfgs
+                    .0
+                    .iter()
+                    .any(|c| c.key_pair() == Some((name.as_str(), value.as_str()))))
+            }
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Ok(self.cfgs.0.iter().any(|c| c.name() == Some(name.as_str()))),
         }
-        for rustc_target in &self.rustc_targets {
-            cmd.arg("--target");
-            cmd.arg(rustc_target);
+    }
+
+    fn list(&mut self) -> Result<Vec<bool>, Error> {
+        self.expect('(')?;
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(')') {
+                break;
+            }
+            values.push(self.predicate()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(')') => break,
+                _ => return Err(self.error("expected `,` or `)`")),
+            }
         }
-        cmd.arg("--print");
-        cmd.arg("cfg");
-        if !self.rustc_args.is_empty() {
-            cmd.arg("--");
-            cmd.args(&self.rustc_args);
+        self.expect(')')?;
+        Ok(values)
+    }
+
+    fn identifier(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        let rest = &self.input[self.position..];
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !(c == '_' || c.is_alphabetic() || (i > 0 && c.is_alphanumeric())))
+            .map_or(rest.len(), |(i, _)| i);
+        if len == 0 {
+            return Err(self.error("expected a name"));
         }
-        let output = cmd.output()?;
-        if !output.status.success() {
-            return Err(Error::Command(output));
+        self.position += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        if self.peek() != Some('"') {
+            return Err(self.error("expected a string enclosed in double quotes"));
         }
-        let stdout = String::from_utf8(output.stdout)?;
-        let mut cfgs = Vec::new();
-        let mut targets = Vec::new();
-        for line in stdout.lines() {
-            if line.is_empty() {
-                targets.push(RustcTargetCfg(cfgs.drain(..).collect()));
-            } else {
-                cfgs.push(line.parse::<Cfg>()?);
+        self.position += 1;
+        let mut value = String::new();
+        let mut chars = self.input[self.position..].chars();
+        while let Some(c) = chars.next() {
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(value),
+                '\\' => match chars.next() {
+                    Some(e @ '"') | Some(e @ '\\') => {
+                        self.position += 1;
+                        value.push(e);
+                    }
+                    _ => return Err(self.error("unsupported escape sequence")),
+                },
+                c => value.push(c),
             }
         }
-        targets.push(RustcTargetCfg(cfgs));
-        Ok(targets)
+        Err(self.error("unterminated string"))
     }
-}
 
-/// A container for the compiler (rustc) configurations for a specific compiler
-/// target.
-#[derive(Clone, Debug, PartialEq)]
-pub struct RustcTargetCfg(Vec<Cfg>);
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", expected)));
+        }
+        self.position += expected.len_utf8();
+        Ok(())
+    }
 
-impl RustcTargetCfg {
-    /// Returns an iterator visiting all compiler configurations for the
-    /// compiler (rustc) target.
-    pub fn iter(&self) -> Iter<Cfg> {
-        self.0.iter()
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
     }
 
-    /// Returns a reference to the compiler configuration value with the
-    /// corresponding identifier (ID).
-    ///
-    /// In the case of a name compiler configuration, the name is the value. If
-    /// the compiler configuration is a key-value pair, the value will be
-    /// returned if the key matches the ID.
-    pub fn get(&self, id: &str) -> Option<&str> {
-        self.0.iter().find_map(|c| match c {
-            Cfg::Name(n) => {
-                if n == id {
-                    Some(n.as_ref())
-                } else {
-                    None
-                }
-            }
-            Cfg::KeyPair(k, v) => {
-                if k == id {
-                    Some(v.as_ref())
-                } else {
-                    None
-                }
-            }
-        })
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
     }
 
-    /// Returns `true` if a compiler configuration matches the corresponding identifier (ID).
-    ///
-    /// In the case of a name compiler configuration, the name is the ID. If the
-    /// compiler configuration is a key-value pair, then this will return `true`
-    /// if either the key or the value match the ID.
-    pub fn has(&self, id: &str) -> bool {
-        self.0.iter().any(|c| match c {
-            Cfg::Name(n) => n == id,
-            Cfg::KeyPair(k, v) => k == id || v == id,
-        })
+    fn error(&self, message: &str) -> Error {
+        Error::Generic(format!(
+            "Could not evaluate the '{}' predicate, {} at position {}",
+            self.input, message, self.position
+        ))
     }
 }
 
@@ -785,6 +7422,65 @@ impl FromStr for RustcTargetCfg {
     }
 }
 
+impl AsRef<[Cfg]> for RustcTargetCfg {
+    fn as_ref(&self) -> &[Cfg] {
+        &self.0
+    }
+}
+
+impl Deref for RustcTargetCfg {
+    type Target = [Cfg];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Index<usize> for RustcTargetCfg {
+    type Output = Cfg;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl Index<&str> for RustcTargetCfg {
+    type Output = str;
+
+    fn index(&self, id: &str) -> &Self::Output {
+        self.get(id)
+            .unwrap_or_else(|| panic!("The '{}' compiler configuration does not exist", id))
+    }
+}
+
+impl From<Vec<Cfg>> for RustcTargetCfg {
+    fn from(cfgs: Vec<Cfg>) -> Self {
+        Self(cfgs)
+    }
+}
+
+impl From<RustcTargetCfg> for Vec<Cfg> {
+    fn from(target: RustcTargetCfg) -> Self {
+        target.0
+    }
+}
+
+impl TryFrom<&str> for RustcTargetCfg {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for RustcTargetCfg {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 impl IntoIterator for RustcTargetCfg {
     type Item = Cfg;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -794,9 +7490,21 @@ impl IntoIterator for RustcTargetCfg {
     }
 }
 
+impl<'a> IntoIterator for &'a RustcTargetCfg {
+    type Item = &'a Cfg;
+    type IntoIter = Iter<'a, Cfg>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl fmt::Display for RustcTargetCfg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for cfg in &self.0 {
+        for (i, cfg) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
             cfg.fmt(f)?;
         }
         Ok(())
@@ -808,7 +7516,97 @@ impl fmt::Display for RustcTargetCfg {
 ///
 /// A compiler configuration is either a `Name` configuration, like "unix" or
 /// "debug_assertions", or a `KeyPair` configuration, like `target_os="windows"`.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// The `Display` implementation writes the same format as the compiler, i.e.
+/// `key="value"` for a key-value pair, so the output of the `cargo rustc
+/// --print cfg` command round-trips through the `FromStr` and `Display`
+/// implementations. The `FromStr` implementation applies the following rules:
+///
+/// - Whitespace surrounding the name, key, or value is ignored.
+/// - A line without the equal sign, `=`, is a name configuration, e.g. `foo`.
+/// - A line with an equal sign is always a key-value pair configuration,
+///   where the key is everything before the _first_ equal sign and must not
+///   be empty. The value is everything after it, so it can contain further
+///   equal signs.
+/// - One pair of surrounding double quotes is removed from the value. An
+///   empty value is allowed with or without the double quotes, i.e. both
+///   `foo=""` and `foo=` are `KeyPair("foo", "")`.
+/// - A value with a double quote on only one side is an error.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{Cfg, Error};
+/// # use std::convert::TryFrom;
+/// # fn main() -> std::result::Result<(), Error> {
+/// assert_eq!("unix".parse::<Cfg>()?, Cfg::Name(String::from("unix")));
+/// assert_eq!(
+///     "target_os=\"linux\"".parse::<Cfg>()?,
+///     Cfg::KeyPair(String::from("target_os"), String::from("linux"))
+/// );
+/// assert!(matches!("=\"linux\"".parse::<Cfg>(), Err(Error::Parse { .. })));
+/// assert!(matches!("target_os=\"linux".parse::<Cfg>(), Err(Error::Parse { .. })));
+/// assert_eq!(Cfg::try_from("unix")?, Cfg::Name(String::from("unix")));
+/// assert_eq!(
+///     Cfg::try_from(String::from("target_os=\"linux\""))?,
+///     Cfg::KeyPair(String::from("target_os"), String::from("linux"))
+/// );
+/// assert_eq!(
+///     " target_os = \"linux\" ".parse::<Cfg>()?,
+///     Cfg::KeyPair(String::from("target_os"), String::from("linux"))
+/// );
+/// assert_eq!("foo".parse::<Cfg>()?, Cfg::Name(String::from("foo")));
+/// assert_eq!("foo=".parse::<Cfg>()?, Cfg::KeyPair(String::from("foo"), String::new()));
+/// assert_eq!("foo=\"\"".parse::<Cfg>()?, Cfg::KeyPair(String::from("foo"), String::new()));
+/// assert_eq!(
+///     "foo=\"a=b\"".parse::<Cfg>()?,
+///     Cfg::KeyPair(String::from("foo"), String::from("a=b"))
+/// );
+/// assert!(matches!("foo=\"".parse::<Cfg>(), Err(Error::Parse { .. })));
+/// assert!(matches!("=".parse::<Cfg>(), Err(Error::Parse { .. })));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A key-value pair configuration can also be created infallibly from a tuple
+/// of trusted inputs, which is more concise for test fixtures and synthetic
+/// configurations than the `KeyPair` variant. The validating alternative is
+/// the [`try_key_pair`] method. A string is always parsed with the `TryFrom`
+/// and `FromStr` implementations, so there is no infallible conversion from a
+/// string to a name configuration.
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::Cfg;
+/// assert_eq!(
+///     Cfg::from(("target_os", "linux")),
+///     Cfg::KeyPair(String::from("target_os"), String::from("linux"))
+/// );
+/// let cfg: Cfg = (String::from("target_arch"), String::from("x86_64")).into();
+/// assert_eq!(cfg.key_pair(), Some(("target_arch", "x86_64")));
+/// ```
+///
+/// Every line of the output round-trips:
+///
+/// ```
+/// # extern crate cargo_rustc_cfg;
+/// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Cfg, Error};
+/// # fn main() -> std::result::Result<(), Error> {
+/// let stdout = CargoRustcPrintCfg::default()
+///     .rustc_targets(&["x86_64-unknown-linux-gnu", "thumbv7em-none-eabihf", "wasm32-unknown-unknown"])
+///     .execute_raw()?;
+/// for line in stdout.lines().filter(|l| !l.is_empty()) {
+///     let cfg = line.parse::<Cfg>()?;
+///     assert_eq!(cfg.to_string(), line);
+///     assert_eq!(cfg.to_string().parse::<Cfg>()?, cfg);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`try_key_pair`]: #method.try_key_pair
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Cfg {
     /// A compiler configuration like `unix`, `windows`, `debug_assertions`, etc.
     Name(String),
@@ -819,6 +7617,73 @@ pub enum Cfg {
 }
 
 impl Cfg {
+    /// Creates a key-value pair configuration after validating the key and
+    /// value.
+    ///
+    /// The `KeyPair` variant can still be constructed directly, but it does
+    /// not validate its parts, which may yield a configuration that cannot
+    /// round-trip through the `Display` and `FromStr` implementations. The
+    /// following rules are applied:
+    ///
+    /// - The key must be a non-empty identifier, i.e. it starts with a letter
+    ///   or an underscore, `_`, followed by letters, digits, or underscores.
+    ///   This excludes the equal sign, `=`, double quotes, and whitespace.
+    /// - The value can be empty, but it must not contain a line break, because
+    ///   each configuration is printed on its own line. The equal sign, `=`,
+    ///   and double quotes, `"`, are allowed, because they are escaped by the
+    ///   `Display` implementation.
+    ///
+    /// A `Generic` error is returned if a rule is violated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let cfg = Cfg::try_key_pair("target_os", "linux")?;
+    /// assert_eq!(cfg, Cfg::KeyPair(String::from("target_os"), String::from("linux")));
+    /// assert_eq!(cfg.to_string().parse::<Cfg>()?, cfg);
+    /// assert!(Cfg::try_key_pair("target_abi", "").is_ok());
+    ///
+    /// assert!(matches!(Cfg::try_key_pair("", "linux"), Err(Error::Generic(..))));
+    /// assert!(matches!(Cfg::try_key_pair("target=os", "linux"), Err(Error::Generic(..))));
+    /// assert!(matches!(Cfg::try_key_pair("\"target_os\"", "linux"), Err(Error::Generic(..))));
+    /// assert!(matches!(Cfg::try_key_pair("target os", "linux"), Err(Error::Generic(..))));
+    /// assert!(matches!(Cfg::try_key_pair("1target", "linux"), Err(Error::Generic(..))));
+    /// assert!(matches!(Cfg::try_key_pair("target_os", "li\nux"), Err(Error::Generic(..))));
+    /// assert!(matches!(Cfg::try_key_pair("target_os", "li\rnux"), Err(Error::Generic(..))));
+    ///
+    /// let cfg = Cfg::try_key_pair("target_os", "li\"n=ux")?;
+    /// assert_eq!(cfg.to_string().parse::<Cfg>()?, cfg);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_key_pair<K, V>(key: K, value: V) -> Result<Self, Error>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let key = key.into();
+        let value = value.into();
+        let mut chars = key.chars();
+        let is_identifier = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(Error::Generic(format!(
+                "The '{}' key is not a valid configuration identifier",
+                key
+            )));
+        }
+        if value.contains(['\n', '\r']) {
+            return Err(Error::Generic(format!(
+                "The {:?} value cannot contain a line break",
+                value
+            )));
+        }
+        Ok(Cfg::KeyPair(key, value))
+    }
+
     /// Returns the name configuration.
     ///
     /// This will return `None` if the configuration is not a name
@@ -885,6 +7750,57 @@ impl Cfg {
         }
     }
 
+    /// Checks if this is a name configuration with the name.
+    ///
+    /// Returns `true` if this is a name configuration and the name is equal;
+    /// otherwise, this returns `false`, including for a key-value pair
+    /// configuration with the name as its key or value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_family=\"unix\"\nunix".parse::<RustcTargetCfg>()?;
+    /// assert!(target.iter().any(|c| c.is_name_of("unix")));
+    /// assert!(!Cfg::from(("target_family", "unix")).is_name_of("unix"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_name_of(&self, name: &str) -> bool {
+        match self {
+            Cfg::Name(n) => n == name,
+            Cfg::KeyPair(..) => false,
+        }
+    }
+
+    /// Checks if this is a key-value pair configuration with the key and
+    /// value.
+    ///
+    /// Returns `true` if this is a key-value pair configuration and both the
+    /// key and the value are equal; otherwise, this returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{Cfg, Error, RustcTargetCfg};
+    /// # fn main() -> std::result::Result<(), Error> {
+    /// let target = "target_feature=\"sse2\"\ntarget_os=\"linux\"".parse::<RustcTargetCfg>()?;
+    /// assert!(target.iter().any(|c| c.matches("target_feature", "sse2")));
+    /// assert!(!target.iter().any(|c| c.matches("target_feature", "avx")));
+    /// assert!(!Cfg::Name(String::from("unix")).matches("unix", "unix"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matches(&self, key: &str, value: &str) -> bool {
+        match self {
+            Cfg::Name(..) => false,
+            Cfg::KeyPair(k, v) => k == key && v == value,
+        }
+    }
+
     /// Returns the name configuration.
     ///
     /// This will return `None` if this is not a name configuration. Regardless,
@@ -912,30 +7828,63 @@ impl FromStr for Cfg {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains('=') {
-            let mut parts = s.split('=');
-            if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
-                Ok(Cfg::KeyPair(
-                    String::from(key),
-                    value.trim_matches('"').to_string(),
-                ))
-            } else {
-                Err(Error::Generic(format!(
-                    "Could not parse '{}' into a key-value configuration pair",
-                    s
-                )))
+        let s = s.trim();
+        match s.split_once('=') {
+            Some((key, value)) => {
+                let key = key.trim();
+                let value = value.trim();
+                let unquoted = match (value.strip_prefix('"'), value.ends_with('"')) {
+                    (Some(v), true) => v.strip_suffix('"'),
+                    (None, false) => Some(value),
+                    _ => None,
+                };
+                match unquoted {
+                    Some(value) if !key.is_empty() => {
+                        Ok(Cfg::KeyPair(String::from(key), String::from(value)))
+                    }
+                    _ => Err(Error::Parse {
+                        input: String::from(s),
+                    }),
+                }
             }
-        } else {
-            Ok(Cfg::Name(String::from(s)))
+            None => Ok(Cfg::Name(String::from(s))),
         }
     }
 }
 
+impl TryFrom<&str> for Cfg {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for Cfg {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<(String, String)> for Cfg {
+    fn from((key, value): (String, String)) -> Self {
+        Cfg::KeyPair(key, value)
+    }
+}
+
+impl From<(&str, &str)> for Cfg {
+    fn from((key, value): (&str, &str)) -> Self {
+        Cfg::KeyPair(String::from(key), String::from(value))
+    }
+}
+
 impl fmt::Display for Cfg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Cfg::Name(ref s) => s.fmt(f),
-            Cfg::KeyPair(ref k, ref v) => write!(f, "{} = \"{}\"", k, v),
+            Cfg::KeyPair(ref k, ref v) => write!(f, "{}=\"{}\"", k, v),
         }
     }
 }
@@ -949,27 +7898,183 @@ impl fmt::Display for Cfg {
 pub enum Error {
     /// A command operation failed. Any content in the STDERR stream is used as
     /// part of the error message.
-    Command(Output),
+    ///
+    /// The command line is included, i.e. the program and all of its
+    /// arguments, like the toolchain and targets, so the failure can be
+    /// reproduced by running the same command.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .rustc_target("not-a-real-target")
+    ///     .execute();
+    /// match result {
+    ///     Err(err @ Error::Command { .. }) => {
+    ///         assert!(err.to_string().contains(r#""--target" "not-a-real-target""#));
+    ///         if let Error::Command { command, output } = err {
+    ///             assert!(command.ends_with(r#""--target" "not-a-real-target" "--print" "cfg""#));
+    ///             assert!(!output.status.success());
+    ///         }
+    ///     }
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    /// ```
+    Command {
+        /// The command line that was executed.
+        command: String,
+        /// The output of the failed command.
+        output: Output,
+    },
     /// UTF-8 string conversion failed.
     FromUtf8(std::string::FromUtf8Error),
     /// A generic, or custom, error occurred. The message should contain the detailed information.
     Generic(String),
     /// An I/O operation failed.
     Io(std::io::Error),
+    /// The command failed because of lints denied by a lint level, e.g. from
+    /// the `-D warnings` compiler argument, and not because of an inability to
+    /// print the compiler configurations.
+    ///
+    /// This is only used if every error reported by the command is a denied
+    /// lint, otherwise the [`Error::Command`] is used.
+    ///
+    /// [`Error::Command`]: #variant.Command
+    LintDenied {
+        /// The command line that was executed.
+        command: String,
+        /// The output of the failed command, which contains the denied lints.
+        output: Output,
+    },
+    /// The command requires the nightly toolchain, but a different release
+    /// channel is used.
+    ///
+    /// Unstable Cargo features, like the `--print` argument, are only
+    /// available on the nightly channel.
+    NightlyRequired {
+        /// The release of the toolchain that was used, e.g. `1.85.0`.
+        release: String,
+    },
+    /// The output of a command is not valid UTF-8.
+    ///
+    /// The command is included to help identify which invocation produced the
+    /// invalid output.
+    Output {
+        /// The command that was executed.
+        command: String,
+        /// The underlying UTF-8 conversion error, which contains the byte
+        /// offset of the invalid sequence.
+        source: std::string::FromUtf8Error,
+    },
+    /// A line from the output of the `cargo rustc --print cfg` command, or any
+    /// other input, could not be parsed into a compiler configuration.
+    ///
+    /// This is likely the result of a change in the output format of rustc.
+    Parse {
+        /// The offending line.
+        input: String,
+    },
+    /// The number of compiler configurations in the output does not match the
+    /// number of requested targets, or one for the host.
+    ///
+    /// This is likely the result of a change in the output format of Cargo or
+    /// rustc, which would otherwise silently yield the wrong compiler
+    /// configurations.
+    ///
+    /// # Examples
+    ///
+    /// A mismatch is simulated with a stub for the `cargo` command that only
+    /// prints one compiler configuration:
+    ///
+    /// ```
+    /// # extern crate cargo_rustc_cfg;
+    /// # use cargo_rustc_cfg::{CargoRustcPrintCfg, Error};
+    /// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let stub = std::env::temp_dir().join(format!("cargo-count-stub-{}", std::process::id()));
+    /// # std::fs::write(&stub, "#!/bin/sh\necho 'target_arch=\"x86\"'\n")?;
+    /// # std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+    /// let result = CargoRustcPrintCfg::default()
+    ///     .program(&stub)
+    ///     .rustc_targets(&["i686-pc-windows-msvc", "x86_64-pc-windows-msvc"])
+    ///     .execute();
+    /// match result {
+    ///     Err(Error::TargetCount { expected, found, output }) => {
+    ///         assert_eq!(expected, 2);
+    ///         assert_eq!(found, 1);
+    ///         assert_eq!(output, "target_arch=\"x86\"\n");
+    ///     }
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    /// # std::fs::remove_file(&stub)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    TargetCount {
+        /// The number of requested targets, or one for the host.
+        expected: usize,
+        /// The number of compiler configurations in the output.
+        found: usize,
+        /// The raw output, for debugging.
+        output: String,
+    },
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Command(output) => write!(
+            Self::Command { command, output } => write!(
                 f,
-                "{:?}: {}",
-                output,
+                "{} failed with {}: {}",
+                command,
+                output.status,
                 String::from_utf8_lossy(&output.stderr)
             ),
             Self::FromUtf8(err) => err.fmt(f),
             Self::Generic(msg) => write!(f, "{}", msg),
             Self::Io(err) => err.fmt(f),
+            Self::LintDenied { command, output } => write!(
+                f,
+                "{} failed because of denied lints, not an inability to print the compiler \
+                 configurations, consider removing deny flags like `-D warnings`: {}",
+                command,
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            Self::NightlyRequired { release } => write!(
+                f,
+                "The nightly toolchain is required, but the {} release is used. Install the \
+                 nightly toolchain with `rustup toolchain install nightly` and select it, e.g. \
+                 with the `+nightly` toolchain argument",
+                release
+            ),
+            Self::Output { command, source } => write!(
+                f,
+                "The output from {} is not valid UTF-8 at byte {}: {}",
+                command,
+                source.utf8_error().valid_up_to(),
+                source
+            ),
+            Self::Parse { input } => write!(
+                f,
+                "Could not parse '{}' into a compiler configuration",
+                input
+            ),
+            Self::TargetCount {
+                expected,
+                found,
+                output,
+            } => write!(
+                f,
+                "Expected {} compiler configurations, but found {}, STDOUT: '{}'",
+                expected,
+                found,
+                snippet(output)
+            ),
         }
     }
 }
@@ -977,10 +8082,15 @@ impl std::fmt::Display for Error {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Command(..) => None,
+            Self::Command { .. } => None,
             Self::FromUtf8(err) => Some(err),
             Self::Generic(..) => None,
             Self::Io(err) => Some(err),
+            Self::LintDenied { .. } => None,
+            Self::NightlyRequired { .. } => None,
+            Self::Output { source, .. } => Some(source),
+            Self::Parse { .. } => None,
+            Self::TargetCount { .. } => None,
         }
     }
 }